@@ -0,0 +1,269 @@
+//! Implements the shared deserialization context and the coercions
+
+use crate::{
+    de::{
+        options::{ Coercion, NameMatching, Options, Report },
+        trail::Trail
+    },
+    error::{ Error, Result },
+    typing::{ AnyValue, Boolean, Bytes, Enumeration, Float, Integer, Kind, Map, Number, Sequence, Utf8String }
+};
+use alloc::{ format, string::String, vec::Vec };
+use core::{ cell::{ Cell, RefCell }, convert::TryFrom };
+
+
+/// The context that is shared across all (sub-)deserializers of a deserialization run
+#[derive(Debug, Default)]
+pub struct Context {
+    /// The deserialization options
+    options: Options,
+    /// The report of the current deserialization run
+    report: RefCell<Report>,
+    /// The coercions of the current conversion which are recorded once the conversion succeeds
+    pending: RefCell<Vec<Coercion>>,
+    /// The amount of values that have been visited so far
+    nodes: Cell<usize>
+}
+impl Context {
+    /// Creates a new deserialization context
+    pub fn new(options: Options) -> Self {
+        Self { options, report: RefCell::default(), pending: RefCell::default(), nodes: Cell::default() }
+    }
+
+    /// The deserialization options
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+    /// Takes the report of the current deserialization run
+    pub fn take_report(&self) -> Report {
+        self.report.take()
+    }
+
+    /// Gets `value` as boolean and applies the allowed coercions if necessary
+    pub fn boolean(&self, value: AnyValue) -> Result<Boolean> {
+        match value {
+            AnyValue::Integer(integer) if self.options.allows(Coercion::IntegerToBool) => match integer.canonical() {
+                Integer::UnsignedInteger(0) => self.coerced(Coercion::IntegerToBool, false.into()),
+                Integer::UnsignedInteger(1) => self.coerced(Coercion::IntegerToBool, true.into()),
+                _ => Boolean::try_from(AnyValue::Integer(integer))
+            },
+            value => Boolean::try_from(value)
+        }
+    }
+    /// Gets `value` as integer and applies the allowed coercions if necessary
+    pub fn integer(&self, value: AnyValue) -> Result<Integer> {
+        match value {
+            AnyValue::Float(float) if self.options.allows(Coercion::FloatToInteger) => {
+                let integer = Number::Float(float).to_integer()?;
+                self.coerced(Coercion::FloatToInteger, integer)
+            },
+            AnyValue::Utf8String(string) if self.options.allows(Coercion::StringToInteger) => {
+                let integer = match string.trim() {
+                    string if string.starts_with('-') => string.parse().map(Integer::SignedInteger),
+                    string => string.parse().map(Integer::UnsignedInteger)
+                };
+                let integer = integer.map_err(|_| emismatch!(Kind::Integer, AnyValue::Utf8String(string.clone())))?;
+                self.coerced(Coercion::StringToInteger, integer)
+            },
+            value => Integer::try_from(value)
+        }
+    }
+    /// Gets `value` as float and applies the allowed coercions if necessary
+    pub fn float(&self, value: AnyValue) -> Result<Float> {
+        match value {
+            AnyValue::Integer(integer) if self.options.allows(Coercion::IntegerToFloat) => {
+                let float = Number::Integer(integer).as_f64();
+                self.coerced(Coercion::IntegerToFloat, float.into())
+            },
+            AnyValue::Utf8String(string) if self.options.allows(Coercion::StringToFloat) => {
+                let float: f64 = string.trim().parse()
+                    .map_err(|_| emismatch!(Kind::Float, AnyValue::Utf8String(string.clone())))?;
+                self.coerced(Coercion::StringToFloat, float.into())
+            },
+            value => Float::try_from(value)
+        }
+    }
+    /// Gets `value` as UTF-8 string and applies the allowed coercions if necessary
+    pub fn utf8_string(&self, value: AnyValue) -> Result<Utf8String> {
+        match value {
+            AnyValue::Integer(Integer::UnsignedInteger(integer)) if self.options.allows(Coercion::IntegerToString) => {
+                self.coerced(Coercion::IntegerToString, Utf8String::new(integer))
+            },
+            AnyValue::Integer(Integer::SignedInteger(integer)) if self.options.allows(Coercion::IntegerToString) => {
+                self.coerced(Coercion::IntegerToString, Utf8String::new(integer))
+            },
+            AnyValue::Float(float) if self.options.allows(Coercion::FloatToString) => {
+                // Use the debug representation which always keeps the fractional part (e.g. `1.0` instead of `1`)
+                self.coerced(Coercion::FloatToString, Utf8String::from(format!("{:?}", *float)))
+            },
+            value => Utf8String::try_from(value)
+        }
+    }
+    /// Gets `value` as bytes and applies the allowed coercions if necessary
+    pub fn bytes(&self, value: AnyValue) -> Result<Bytes> {
+        match value {
+            AnyValue::Sequence(sequence) if self.options.allows(Coercion::SequenceToBytes) => {
+                let bytes = Bytes::try_from(sequence)?;
+                self.coerced(Coercion::SequenceToBytes, bytes)
+            },
+            value => Bytes::try_from(value)
+        }
+    }
+    /// Gets `value` as tuple where bytes are converted into a sequence of integers
+    pub fn tuple(&self, value: AnyValue) -> Result<Sequence> {
+        match value {
+            AnyValue::Bytes(bytes) => Ok(Sequence::from(bytes)),
+            value => Sequence::try_from(value)
+        }
+    }
+    /// Gets `value` as sequence where bytes are converted into a sequence of integers and applies the allowed
+    /// coercions if necessary
+    pub fn sequence(&self, value: AnyValue) -> Result<Sequence> {
+        match value {
+            AnyValue::Sequence(sequence) => Ok(sequence),
+            AnyValue::Bytes(bytes) => Ok(Sequence::from(bytes)),
+            AnyValue::None => Sequence::try_from(AnyValue::None),
+            value if self.options.allows(Coercion::ValueToSequence) => {
+                self.coerced(Coercion::ValueToSequence, Sequence::new([value]))
+            },
+            value => Sequence::try_from(value)
+        }
+    }
+    /// Gets `value` as map and applies the allowed coercions if necessary
+    pub fn map(&self, value: AnyValue) -> Result<Map> {
+        match value {
+            AnyValue::Sequence(pairs) if self.options.allows(Coercion::PairsToMap) && pairs.iter().all(is_pair) => {
+                let mut map = Map::default();
+                for pair in pairs {
+                    let [key, value] = <[AnyValue; 2]>::try_from(Sequence::try_from(pair)?)?;
+                    if map.contains_key(&key) {
+                        Err(einval!("The sequence of pairs contains the key {:?} multiple times", key))?;
+                    }
+                    map.insert(key, value);
+                }
+                self.coerced(Coercion::PairsToMap, map)
+            },
+            value => Map::try_from(value)
+        }
+    }
+    /// Parses the string map key `key` into `kind` if allowed, or returns it unmodified otherwise
+    pub fn map_key(&self, key: AnyValue, kind: Kind) -> Result<AnyValue> {
+        let string = match &key {
+            AnyValue::Utf8String(string) if self.options.allows(Coercion::ParseStringKeys) => string.trim(),
+            _ => return Ok(key)
+        };
+        let parsed = match kind {
+            Kind::Bool => string.parse().ok().map(|bool: bool| AnyValue::Bool(bool.into())),
+            Kind::Integer if string.starts_with('-') => string.parse().ok().map(Integer::SignedInteger).map(Into::into),
+            Kind::Integer => string.parse().ok().map(Integer::UnsignedInteger).map(Into::into),
+            Kind::Float => string.parse().ok().map(|float: f64| AnyValue::Float(float.into())),
+            _ => None
+        };
+        match parsed {
+            Some(parsed) => self.coerced(Coercion::ParseStringKeys, parsed),
+            // Note: keys that cannot be parsed are rejected by the subsequent conversion
+            None => Ok(key)
+        }
+    }
+    /// Renames the keys of `map` to the matching struct `fields` according to the name matching strategy
+    pub fn match_fields(&self, map: Map, fields: &[&'static str]) -> Result<Map> {
+        let name_matching = self.options.name_matching();
+        if name_matching == NameMatching::Exact {
+            return Ok(map);
+        }
+
+        let mut matched = Map::default();
+        for (key, value) in map {
+            let name = match key {
+                AnyValue::Utf8String(name) => name,
+                key => {
+                    matched.insert(key, value);
+                    continue;
+                }
+            };
+
+            // Rename the key and ensure that there is only one key per field
+            let name = match name_matching.find(&name, fields)? {
+                Some(field) => Utf8String::from(field),
+                None => name
+            };
+            if matched.contains_key(&AnyValue::Utf8String(name.clone())) {
+                Err(einval!("Multiple map keys match the field {:?}", name.as_str()))?;
+            }
+            matched.insert(AnyValue::Utf8String(name), value);
+        }
+        Ok(matched)
+    }
+    /// Gets `value` as enum according to the accepted enum representation and renames the variant to the matching
+    /// `variants` according to the name matching strategy
+    pub fn enumeration(&self, value: AnyValue, variants: &[&'static str]) -> Result<Enumeration> {
+        let mut enumeration = self.options.enum_repr().decode(value)?;
+        if let Some(variant) = self.options.name_matching().find(enumeration.variant(), variants)? {
+            *enumeration.variant_mut() = String::from(variant);
+        }
+        Ok(enumeration)
+    }
+
+    /// Registers `value` at the location `trail` and enforces the limits
+    pub fn enter(&self, value: &AnyValue, trail: &Trail) -> Result {
+        let nodes = self.nodes.get() + 1;
+        self.nodes.set(nodes);
+
+        // Check the limits
+        let limits = self.options.limits();
+        limits.check_depth(trail.depth())?;
+        limits.check_nodes(nodes)?;
+        match value {
+            AnyValue::Utf8String(string) => limits.check_string_len(string.len()),
+            AnyValue::Bytes(bytes) => limits.check_bytes_len(bytes.len()),
+            AnyValue::Sequence(sequence) => limits.check_collection_len(sequence.len()),
+            AnyValue::Map(map) => limits.check_collection_len(map.len()),
+            _ => Ok(())
+        }
+    }
+
+    /// Records `error` at the location `trail` if errors are collected, or returns it otherwise
+    pub fn recover(&self, error: Error, trail: &Trail) -> Result {
+        if !self.options.collects_errors() {
+            return Err(error);
+        }
+        self.report.borrow_mut().errors.push(error.with_path(trail.to_path()));
+        Ok(())
+    }
+
+    /// Records the location `trail` as ignored if ignored values are tracked and the location is not within an already
+    /// ignored value
+    pub fn ignored(&self, trail: &Trail) {
+        if !self.options.tracks_ignored() {
+            return;
+        }
+
+        // Values are visited depth-first, so a parent location is always the last recorded one
+        let path = trail.to_path();
+        let mut report = self.report.borrow_mut();
+        match report.ignored.last() {
+            Some(last) if path.starts_with(last) => (),
+            _ => report.ignored.push(path)
+        }
+    }
+
+    /// Records the pending coercions of the current conversion if it has succeeded, or discards them otherwise
+    pub fn finish_conversion(&self, succeeded: bool) {
+        let pending = self.pending.take();
+        if succeeded {
+            self.report.borrow_mut().coercions.extend(pending);
+        }
+    }
+
+    /// Marks `coercion` as pending for the current conversion and returns `value`
+    fn coerced<T>(&self, coercion: Coercion, value: T) -> Result<T> {
+        self.pending.borrow_mut().push(coercion);
+        Ok(value)
+    }
+}
+
+
+/// Whether `value` is a sequence with exactly two elements
+fn is_pair(value: &AnyValue) -> bool {
+    matches!(value, AnyValue::Sequence(sequence) if sequence.len() == 2)
+}
@@ -0,0 +1,704 @@
+//! Implements the deserialization from the simplified data structure
+
+mod context;
+mod options;
+mod trail;
+
+use crate::{
+    de::{ context::Context, trail::Trail },
+    error::{ Result, Error },
+    limits::Limits,
+    path::{ Path, Segment },
+    repr::EnumRepr,
+    tree,
+    typing::{ AnyValue, Enumeration, Integer, Kind, Map, Sequence, Utf8String }
+};
+use serde::{
+    Deserialize, Deserializer as _,
+    de::{ self, DeserializeSeed, IntoDeserializer, Visitor, SeqAccess, MapAccess, VariantAccess, EnumAccess }
+};
+use alloc::{ rc::Rc, string::String, vec::Vec };
+use core::{
+    mem, result,
+    convert::{ TryFrom, TryInto },
+    iter::FromIterator
+};
+// Reexport types
+pub use crate::de::options::{ Coercion, NameMatching, Options, Report };
+
+
+/// A sequence reader
+struct SequenceReader {
+    /// The sequence objects
+    values: <Sequence as IntoIterator>::IntoIter,
+    /// The index of the next sequence object
+    index: usize,
+    /// The location of the sequence
+    trail: Trail,
+    /// The deserialization context
+    context: Rc<Context>
+}
+impl SequenceReader {
+    /// Creates a new sequence reader
+    pub fn new(sequence: Sequence, trail: Trail, context: Rc<Context>) -> Self {
+        Self { values: sequence.into_iter(), index: 0, trail, context }
+    }
+
+    /// Ensures in strict mode that all sequence elements have been consumed and records the unconsumed elements
+    /// otherwise
+    fn finish(self) -> Result {
+        match self.values.len() {
+            remaining if remaining > 0 && self.context.options().is_strict() => {
+                let error = einval!("{} trailing sequence elements have not been consumed", remaining);
+                Err(error.prepend_path(Segment::Index(self.index)))
+            },
+            remaining => {
+                for index in self.index..self.index + remaining {
+                    self.context.ignored(&self.trail.child(Segment::Index(index)));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+impl<'de> SeqAccess<'de> for SequenceReader {
+    type Error = Error;
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>> where T: DeserializeSeed<'de> {
+        // Check if there are sequence objects left
+        let value = match self.values.next() {
+            Some(value) => value,
+            None => return Ok(None)
+        };
+
+        // Deserialize the value
+        let trail = self.trail.child(Segment::Index(self.index));
+        self.index += 1;
+        let deserialized = Deserializer::nested(value, trail.clone(), self.context.clone())
+            .and_then(|deserializer| seed.deserialize(deserializer))
+            .map_err(|e| located(e, &trail))?;
+        Ok(Some(deserialized))
+    }
+}
+
+
+/// A key value reader
+struct KeyValueReader {
+    /// The next pending value together with its location
+    pending_value: Option<(AnyValue, Trail)>,
+    /// The underlying map to read from
+    values: <Map as IntoIterator>::IntoIter,
+    /// The struct fields requested by the visitor or an empty slice if the map is not read as struct
+    fields: &'static [&'static str],
+    /// The location of the map
+    trail: Trail,
+    /// The deserialization context
+    context: Rc<Context>
+}
+impl KeyValueReader {
+    /// Creates a new map reader
+    pub fn new(map: Map, fields: &'static [&'static str], trail: Trail, context: Rc<Context>) -> Self {
+        Self { pending_value: None, values: map.into_iter(), fields, trail, context }
+    }
+
+    /// The path segment of the entry with `key`, i.e. the field name if `key` is a requested struct field
+    fn segment(&self, key: AnyValue) -> Segment {
+        match key {
+            AnyValue::Utf8String(name) if self.fields.contains(&name.as_str()) => Segment::Field(name.into()),
+            key => Segment::Key(key)
+        }
+    }
+
+    /// Ensures in strict mode that all map entries have been consumed and records the unconsumed entries otherwise
+    fn finish(mut self) -> Result {
+        if self.context.options().is_strict() {
+            if let Some((_, trail)) = &self.pending_value {
+                Err(located(einval!("The map value has not been consumed"), trail))?;
+            }
+            return match self.values.next() {
+                None => Ok(()),
+                Some((key, _)) => {
+                    let remaining = self.values.len() + 1;
+                    let error = einval!("{} trailing map entries have not been consumed", remaining);
+                    Err(error.prepend_path(self.segment(key)))
+                }
+            };
+        }
+
+        // Record the unconsumed entries
+        if let Some((_, trail)) = self.pending_value.take() {
+            self.context.ignored(&trail);
+        }
+        while let Some((key, _)) = self.values.next() {
+            self.context.ignored(&self.trail.child(self.segment(key)));
+        }
+        Ok(())
+    }
+}
+impl<'de> MapAccess<'de> for KeyValueReader {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>> where K: DeserializeSeed<'de> {
+        // Get the next key
+        if self.pending_value.is_some() {
+            Err(eserde!("Cannot read the next map key while the previous value has not been read"))?;
+        }
+        let (key, value) = match self.values.next() {
+            Some((key, value)) => (key, value),
+            None => return Ok(None)
+        };
+        let trail = self.trail.child(self.segment(key.clone()));
+        self.pending_value = Some((value, trail.clone()));
+
+        // Get the next key
+        let deserialized = Deserializer::nested(key, trail.clone(), self.context.clone())
+            .and_then(|deserializer| seed.deserialize(deserializer.map_key()))
+            .map_err(|e| located(e, &trail))?;
+        Ok(Some(deserialized))
+    }
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value> where V: DeserializeSeed<'de> {
+        // Get the next value and deserialize it
+        let (value, trail) = self.pending_value.take()
+            .ok_or_else(|| eserde!("Cannot read a map value without a preceding key"))?;
+        Deserializer::nested(value, trail.clone(), self.context.clone())
+            .and_then(|deserializer| seed.deserialize(deserializer))
+            .map_err(|e| located(e, &trail))
+    }
+}
+
+
+/// An enum variant reader
+struct EnumValueReader {
+    /// The enum value
+    value: AnyValue,
+    /// The location of the enum value
+    trail: Trail,
+    /// The deserialization context
+    context: Rc<Context>
+}
+impl EnumValueReader {
+    /// Creates a new enum value reader that processes `value`
+    pub const fn new(value: AnyValue, trail: Trail, context: Rc<Context>) -> Self {
+        Self { value, trail, context }
+    }
+}
+impl<'de> VariantAccess<'de> for EnumValueReader {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result {
+        match self.value {
+            AnyValue::None => Ok(()),
+            value => Err(located(emismatch!(Kind::None, value), &self.trail))
+        }
+    }
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value> where T: DeserializeSeed<'de> {
+        let trail = self.trail.clone();
+        Deserializer::nested(self.value, self.trail, self.context)
+            .and_then(|deserializer| seed.deserialize(deserializer))
+            .map_err(|e| located(e, &trail))
+    }
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        let trail = self.trail.clone();
+        Deserializer::nested(self.value, self.trail, self.context)
+            .and_then(|deserializer| deserializer.deserialize_tuple(len, visitor))
+            .map_err(|e| located(e, &trail))
+    }
+    fn struct_variant<V>(self, fields: &'static [&'static str], visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        // Note: internally tagged struct variants without fields are decoded without an associated value
+        let value = match self.value {
+            AnyValue::None => AnyValue::Map(Map::default()),
+            value => value
+        };
+        let trail = self.trail.clone();
+        Deserializer::nested(value, self.trail, self.context)
+            .and_then(|deserializer| deserializer.deserialize_struct("/* unused */", fields, visitor))
+            .map_err(|e| located(e, &trail))
+    }
+}
+
+
+/// An enum variant reader
+struct EnumReader {
+    /// The enum itself
+    enumeration: Enumeration,
+    /// The location of the enum
+    trail: Trail,
+    /// The deserialization context
+    context: Rc<Context>
+}
+impl EnumReader {
+    /// Creates a new enum reader that processes `enumeration`
+    pub const fn new(enumeration: Enumeration, trail: Trail, context: Rc<Context>) -> Self {
+        Self { enumeration, trail, context }
+    }
+}
+impl<'de> EnumAccess<'de> for EnumReader {
+    type Error = Error;
+    type Variant = EnumValueReader;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant)> where V: DeserializeSeed<'de> {
+        // Destructure the enum
+        let (variant, value) = self.enumeration.into_inner();
+        let trail = self.trail.child(Segment::Variant(String::from(variant.clone())));
+
+        // Deserialize the variant and
+        let deserializer = Deserializer::nested(AnyValue::Utf8String(variant), self.trail, self.context.clone())?;
+        let variant = seed.deserialize(deserializer)?;
+        Ok((variant, EnumValueReader::new(value, trail, self.context)))
+    }
+}
+
+
+/// A handle to the report of a deserialization run
+pub struct ReportHandle {
+    /// The deserialization context
+    context: Rc<Context>
+}
+impl ReportHandle {
+    /// Takes the report that has been collected so far
+    pub fn take(&self) -> Report {
+        self.context.take_report()
+    }
+}
+
+
+/// A deserializer for `AnyValue`
+///
+/// # Note
+/// The report of a deserialization run (see `Options::collect_errors` and `Options::track_ignored`) is not returned by
+/// `deserialize`; obtain a handle via `Deserializer::report` before deserializing the value to inspect it afterwards.
+///
+/// # Example
+/// ```
+/// # use serde::Deserialize;
+/// # use serde_dynamic_typing::{ de::Deserializer, AnyValue, Integer, Limits };
+/// let value = AnyValue::Integer(Integer::from(7u8));
+/// let deserializer = Deserializer::new(value).with_limits(Limits::new().max_depth(16));
+/// let value = Option::<u8>::deserialize(deserializer).expect("Failed to deserialize value");
+/// assert_eq!(value, Some(7));
+/// ```
+pub struct Deserializer {
+    /// The value to deserialize
+    value: AnyValue,
+    /// The location of the value
+    trail: Trail,
+    /// The deserialization context
+    context: Rc<Context>,
+    /// Whether the value has already been registered within the context or not
+    entered: bool,
+    /// Whether the value is a map key or not
+    key: bool
+}
+impl Deserializer {
+    /// Creates a new deserializer for `value` with the default options
+    pub fn new(value: AnyValue) -> Self {
+        Self::with_options(value, Options::default())
+    }
+    /// Creates a new deserializer for `value` with the given options
+    pub fn with_options(value: AnyValue, options: Options) -> Self {
+        Self { value, trail: Trail::root(), context: Rc::new(Context::new(options)), entered: false, key: false }
+    }
+
+    /// Sets whether the deserializer announces a human readable format to the deserialized types or not
+    pub fn human_readable(self, human_readable: bool) -> Self {
+        self.configure(|options| options.human_readable(human_readable))
+    }
+    /// Sets the limits to enforce during deserialization
+    pub fn with_limits(self, limits: Limits) -> Self {
+        self.configure(|options| options.with_limits(limits))
+    }
+    /// Sets the accepted representation of Rust enums
+    pub fn with_enum_repr(self, enum_repr: EnumRepr) -> Self {
+        self.configure(|options| options.with_enum_repr(enum_repr))
+    }
+    /// The deserialization options
+    pub fn options(&self) -> &Options {
+        self.context.options()
+    }
+    /// A handle to the report of the deserialization run
+    ///
+    /// # Note
+    /// Changing the options afterwards starts a new deserialization run with a new report.
+    pub fn report(&self) -> ReportHandle {
+        ReportHandle { context: self.context.clone() }
+    }
+
+    /// Replaces the options with `configure(options)`
+    fn configure<F>(self, configure: F) -> Self where F: FnOnce(Options) -> Options {
+        let options = configure(self.options().clone());
+        Self::with_options(self.value, options)
+    }
+    /// Creates a new deserializer that processes the nested `value` and enforces the limits
+    fn nested(value: AnyValue, trail: Trail, context: Rc<Context>) -> Result<Self> {
+        context.enter(&value, &trail)?;
+        Ok(Self { value, trail, context, entered: true, key: false })
+    }
+    /// Marks the value as map key
+    fn map_key(mut self) -> Self {
+        self.key = true;
+        self
+    }
+    /// Registers the value within the context and enforces the limits if this has not been done yet
+    fn enter(&mut self) -> Result {
+        if !self.entered {
+            self.context.enter(&self.value, &self.trail)?;
+            self.entered = true;
+        }
+        Ok(())
+    }
+
+    /// Parses the value into `kind` if it is a string map key and parsing string keys is allowed
+    fn parse_key(&mut self, kind: Kind) -> Result {
+        if self.key {
+            let key = mem::replace(&mut self.value, AnyValue::None);
+            self.value = self.context.map_key(key, kind)?;
+        }
+        Ok(())
+    }
+    /// Takes the value and converts it using `convert`; if the conversion fails and errors are collected, the error is
+    /// recorded and a default value is substituted
+    fn convert<T, F>(&mut self, convert: F) -> Result<T> where T: Default, F: FnOnce(AnyValue, &Context) -> Result<T> {
+        self.enter()?;
+        let value = mem::replace(&mut self.value, AnyValue::None);
+        let converted = convert(value, &self.context);
+        self.context.finish_conversion(converted.is_ok());
+        match converted {
+            Ok(value) => Ok(value),
+            Err(e) => self.context.recover(e, &self.trail).map(|_| T::default())
+        }
+    }
+
+    /// Visits `sequence` and ensures in strict mode that it has `len` elements if given and that all elements have been
+    /// consumed
+    fn visit_sequence<'de, V>(self, sequence: Sequence, len: Option<usize>, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        match len {
+            Some(len) if self.context.options().is_strict() && sequence.len() != len => {
+                Err(einval!("Expected a sequence with {} elements, but found {} elements", len, sequence.len()))
+            },
+            _ => {
+                let mut reader = SequenceReader::new(sequence, self.trail, self.context);
+                let value = visitor.visit_seq(&mut reader)?;
+                reader.finish()?;
+                Ok(value)
+            }
+        }
+    }
+    /// Visits `map` and ensures in strict mode that all entries have been consumed
+    fn visit_map<'de, V>(self, map: Map, fields: &'static [&'static str], visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        let mut reader = KeyValueReader::new(map, fields, self.trail, self.context);
+        let value = visitor.visit_map(&mut reader)?;
+        reader.finish()?;
+        Ok(value)
+    }
+}
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn deserialize_any<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        // Note: buffering visitors (e.g. for `serde(flatten)` or untagged enums) support neither 128 bit integers nor
+        // `visit_enum`, so we use the narrowest 64 bit visit method and present enums as externally tagged values
+        match self.value {
+            AnyValue::Bool(_) => self.deserialize_bool(visitor),
+            AnyValue::Integer(Integer::UnsignedInteger(value)) => match u64::try_from(value) {
+                Ok(_) => self.deserialize_u64(visitor),
+                Err(_) => self.deserialize_u128(visitor)
+            },
+            AnyValue::Integer(Integer::SignedInteger(value)) => match i64::try_from(value) {
+                Ok(_) => self.deserialize_i64(visitor),
+                Err(_) => self.deserialize_i128(visitor)
+            },
+            AnyValue::Float(_) => self.deserialize_f64(visitor),
+            AnyValue::Utf8String(_) => self.deserialize_string(visitor),
+            AnyValue::Bytes(_) => self.deserialize_byte_buf(visitor),
+            AnyValue::Sequence(_) => self.deserialize_seq(visitor),
+            AnyValue::Map(_) => self.deserialize_map(visitor),
+            AnyValue::Enum(_) => {
+                self.enter()?;
+                let value = mem::replace(&mut self.value, AnyValue::None);
+                let (variant, value) = Enumeration::try_from(value)?.into_inner();
+                match value {
+                    AnyValue::None => visitor.visit_string(variant.into()),
+                    value => {
+                        let map = Map::from_iter([(AnyValue::Utf8String(variant), value)]);
+                        self.visit_map(map, &[], visitor)
+                    }
+                }
+            },
+            AnyValue::None => self.deserialize_unit(visitor)
+        }
+    }
+
+    fn deserialize_bool<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        self.parse_key(Kind::Bool)?;
+        let value = self.convert(|value, context| context.boolean(value))?;
+        visitor.visit_bool(value.into())
+    }
+
+    fn deserialize_i8<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        self.parse_key(Kind::Integer)?;
+        let value = self.convert(|value, context| context.integer(value)?.try_into())?;
+        visitor.visit_i8(value)
+    }
+    fn deserialize_i16<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        self.parse_key(Kind::Integer)?;
+        let value = self.convert(|value, context| context.integer(value)?.try_into())?;
+        visitor.visit_i16(value)
+    }
+    fn deserialize_i32<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        self.parse_key(Kind::Integer)?;
+        let value = self.convert(|value, context| context.integer(value)?.try_into())?;
+        visitor.visit_i32(value)
+    }
+    fn deserialize_i64<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        self.parse_key(Kind::Integer)?;
+        let value = self.convert(|value, context| context.integer(value)?.try_into())?;
+        visitor.visit_i64(value)
+    }
+    fn deserialize_i128<V>(mut self, visitor: V) -> Result<V::Value> where V:Visitor<'de> {
+        self.parse_key(Kind::Integer)?;
+        let value = self.convert(|value, context| context.integer(value)?.try_into())?;
+        visitor.visit_i128(value)
+    }
+
+    fn deserialize_u8<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        self.parse_key(Kind::Integer)?;
+        let value = self.convert(|value, context| context.integer(value)?.try_into())?;
+        visitor.visit_u8(value)
+    }
+    fn deserialize_u16<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        self.parse_key(Kind::Integer)?;
+        let value = self.convert(|value, context| context.integer(value)?.try_into())?;
+        visitor.visit_u16(value)
+    }
+    fn deserialize_u32<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        self.parse_key(Kind::Integer)?;
+        let value = self.convert(|value, context| context.integer(value)?.try_into())?;
+        visitor.visit_u32(value)
+    }
+    fn deserialize_u64<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        self.parse_key(Kind::Integer)?;
+        let value = self.convert(|value, context| context.integer(value)?.try_into())?;
+        visitor.visit_u64(value)
+    }
+    fn deserialize_u128<V>(mut self, visitor:V) -> Result<V::Value> where V:Visitor<'de> {
+        self.parse_key(Kind::Integer)?;
+        let value = self.convert(|value, context| context.integer(value)?.try_into())?;
+        visitor.visit_u128(value)
+    }
+
+    fn deserialize_f32<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        self.parse_key(Kind::Float)?;
+        let value = self.convert(|value, context| context.float(value))?;
+        visitor.visit_f32(f64::from(value) as f32)
+    }
+    fn deserialize_f64<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        self.parse_key(Kind::Float)?;
+        let value = self.convert(|value, context| context.float(value))?;
+        visitor.visit_f64(value.into())
+    }
+
+    fn deserialize_char<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        let value = self.convert(|value, context| context.utf8_string(value)?.try_into())?;
+        visitor.visit_char(value)
+    }
+    fn deserialize_str<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        let value = self.convert(|value, context| context.utf8_string(value))?;
+        visitor.visit_str(&value)
+    }
+    fn deserialize_string<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        let value = self.convert(|value, context| context.utf8_string(value))?;
+        visitor.visit_string(value.into())
+    }
+
+    fn deserialize_bytes<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        let value = self.convert(|value, context| context.bytes(value))?;
+        visitor.visit_bytes(&value)
+    }
+    fn deserialize_byte_buf<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        let value = self.convert(|value, context| context.bytes(value))?;
+        visitor.visit_byte_buf(value.into())
+    }
+
+    fn deserialize_option<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        self.enter()?;
+        match self.value {
+            AnyValue::None => visitor.visit_none(),
+            _ => visitor.visit_some(self)
+        }
+    }
+    fn deserialize_unit<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        self.convert(|value, _| <()>::try_from(value))?;
+        visitor.visit_unit()
+    }
+    fn deserialize_unit_struct<V>(mut self, _name: &'static str, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        self.convert(|value, _| <()>::try_from(value))?;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        let value = self.convert(|value, context| context.sequence(value))?;
+        self.visit_sequence(value, None, visitor)
+    }
+    fn deserialize_tuple<V>(mut self, len: usize, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        let value = self.convert(|value, context| context.tuple(value))?;
+        self.visit_sequence(value, Some(len), visitor)
+    }
+    fn deserialize_tuple_struct<V>(mut self, _name: &'static str, len: usize, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        let value = self.convert(|value, context| context.tuple(value))?;
+        self.visit_sequence(value, Some(len), visitor)
+    }
+
+    fn deserialize_map<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        let value = self.convert(|value, context| context.map(value))?;
+        self.visit_map(value, &[], visitor)
+    }
+    fn deserialize_struct<V>(mut self, _name: &'static str, fields: &'static [&'static str], visitor: V)
+        -> Result<V::Value> where V: Visitor<'de>
+    {
+        let value = self.convert(|value, _| Map::try_from(value))?;
+        let value = self.context.match_fields(value, fields)?;
+        self.visit_map(value, fields, visitor)
+    }
+    fn deserialize_enum<V>(mut self, _name: &'static str, variants: &'static [&'static str], visitor: V)
+        -> Result<V::Value> where V: Visitor<'de>
+    {
+        self.enter()?;
+        let value = self.context.enumeration(self.value, variants)?;
+        visitor.visit_enum(EnumReader::new(value, self.trail, self.context))
+    }
+    fn deserialize_identifier<V>(mut self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        self.enter()?;
+        let value = Utf8String::try_from(self.value)?;
+        visitor.visit_string(value.into())
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value> where V: Visitor<'de> {
+        self.context.ignored(&self.trail);
+        self.deserialize_any(visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.options().is_human_readable()
+    }
+}
+impl<'de> IntoDeserializer<'de, Error> for AnyValue {
+    type Deserializer = Deserializer;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        Deserializer::new(self)
+    }
+}
+
+
+/// Prepends the last segment of `trail` to the location of `error`
+fn located(error: Error, trail: &Trail) -> Error {
+    match trail.segment() {
+        Some(segment) => error.prepend_path(segment.clone()),
+        None => error
+    }
+}
+
+
+/// Deserializes a Rust value from a simplified data structure
+pub fn from_typed<'a, T>(value: AnyValue) -> Result<T> where T: Deserialize<'a> {
+    let (value, _report) = from_typed_with(value, &Options::default())?;
+    Ok(value)
+}
+/// Deserializes a Rust value from a simplified data structure using the given options and returns it together with a
+/// report about the deserialization run
+///
+/// # Note
+/// If errors are collected (see `Options::collect_errors`), the returned value may contain substituted default values;
+/// check `Report::errors` to see if this is the case.
+pub fn from_typed_with<'a, T>(value: AnyValue, options: &Options) -> Result<(T, Report)> where T: Deserialize<'a> {
+    let (value, report) = deserialize(value, options.clone());
+    Ok((value?, report))
+}
+/// Deserializes a Rust value from a simplified data structure after filling missing map entries and `AnyValue::None`
+/// values with the corresponding values from `defaults` (see `tree::fill_defaults`)
+pub fn from_typed_with_defaults<'a, T>(value: AnyValue, defaults: &AnyValue) -> Result<T> where T: Deserialize<'a> {
+    let value = tree::fill_defaults(value, defaults);
+    from_typed(value)
+}
+/// Deserializes a Rust value from a simplified data structure using the given options, but instead of failing at the
+/// first error, all errors are collected and returned together
+///
+/// Invalid values are substituted by the default value of the requested type so that the deserialization can keep
+/// going. Errors which cannot be recovered from this way (e.g. a missing struct field or an unknown enum variant) still
+/// stop the deserialization, but are returned together with all errors that have been collected so far.
+pub fn from_typed_collecting<'a, T>(value: AnyValue, options: &Options) -> result::Result<T, Vec<Error>>
+    where T: Deserialize<'a>
+{
+    let options = options.clone().collect_errors(true);
+    let (value, report) = deserialize(value, options);
+
+    // Merge the collected errors with the final result
+    let mut errors = report.errors;
+    match value {
+        Ok(value) if errors.is_empty() => Ok(value),
+        Ok(_) => Err(errors),
+        Err(e) => {
+            errors.push(e);
+            Err(errors)
+        }
+    }
+}
+
+
+/// Deserializes a Rust value from a simplified data structure using the given options and returns it together with the
+/// paths of all values that have been ignored by the visitor (e.g. unknown struct fields) or have never been visited
+///
+/// # Note
+/// Values that are buffered by serde (e.g. for `#[serde(flatten)]` or untagged enums) are consumed as a whole and are
+/// therefore never reported as ignored.
+///
+/// # Example
+/// ```
+/// # use serde_dynamic_typing::{ de::{ self, Options }, AnyValue, Integer, Sequence };
+/// let value = AnyValue::Sequence(Sequence::new([
+///     AnyValue::Integer(Integer::from(80u8)),
+///     AnyValue::Integer(Integer::from(443u16))
+/// ]));
+/// let (value, ignored) = de::from_typed_tracking::<(u16,)>(value, &Options::new()).expect("Invalid value");
+/// assert_eq!(value, (80,));
+/// assert_eq!(ignored[0].to_string(), "$[1]");
+/// ```
+pub fn from_typed_tracking<'a, T>(value: AnyValue, options: &Options) -> Result<(T, Vec<Path>)>
+    where T: Deserialize<'a>
+{
+    let options = options.clone().track_ignored(true);
+    let (value, report) = deserialize(value, options);
+    Ok((value?, report.ignored))
+}
+
+/// Deserializes a Rust value from a simplified data structure into the existing value `place`
+///
+/// # Note
+/// If the deserialization fails, `place` may have been partially updated.
+pub(crate) fn from_typed_in_place<'a, T>(value: AnyValue, place: &mut T) -> Result where T: Deserialize<'a> {
+    let context = Rc::new(Context::new(Options::default()));
+    Deserializer::nested(value, Trail::root(), context)
+        .and_then(|deserializer| T::deserialize_in_place(deserializer, place))
+        .map_err(Error::rooted)
+}
+
+
+/// Deserializes a Rust value from a simplified data structure and returns the result together with the report
+fn deserialize<'a, T>(value: AnyValue, options: Options) -> (Result<T>, Report) where T: Deserialize<'a> {
+    let context = Rc::new(Context::new(options));
+    let value = Deserializer::nested(value, Trail::root(), context.clone())
+        .and_then(|deserializer| T::deserialize(deserializer))
+        .map_err(Error::rooted);
+    (value, context.take_report())
+}
@@ -0,0 +1,248 @@
+//! Implements the configuration and the report of a deserialization run
+
+use crate::{
+    error::{ Error, Result },
+    limits::Limits,
+    path::Path,
+    repr::EnumRepr
+};
+use alloc::{ collections::BTreeSet, string::String, vec::Vec };
+
+
+/// A value coercion which can be applied if the stored type does not match the requested type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Coercion {
+    /// Parses a string like `"42"` into an integer
+    StringToInteger,
+    /// Parses a string like `"4.2"` into a float
+    StringToFloat,
+    /// Formats an integer as string
+    IntegerToString,
+    /// Formats a float as string which always contains a fractional part or an exponent (e.g. `"1.0"` or `"1e300"`) so
+    /// that it can be distinguished from an integer
+    FloatToString,
+    /// Converts an integer into a float (integers above `2^53` are rounded to the nearest float)
+    IntegerToFloat,
+    /// Converts a float into an integer if the float has no fractional part and is within the target range
+    FloatToInteger,
+    /// Converts the integers `0` and `1` into `false` and `true`
+    IntegerToBool,
+    /// Wraps a single non-sequence value into a one-element sequence
+    ValueToSequence,
+    /// Converts a sequence of two-element sequences like `[[key, value], ...]` into a map
+    PairsToMap,
+    /// Parses a string map key like `"42"` or `"true"` into an integer, float or boolean if the requested key type asks
+    /// for it
+    ParseStringKeys,
+    /// Converts a sequence of integers within `0..=255` (e.g. a serialized plain `Vec<u8>`) into bytes
+    SequenceToBytes
+}
+impl Coercion {
+    /// All available coercions
+    pub const ALL: [Self; 11] = [
+        Self::StringToInteger, Self::StringToFloat, Self::IntegerToString, Self::FloatToString,
+        Self::IntegerToFloat, Self::FloatToInteger, Self::IntegerToBool, Self::ValueToSequence,
+        Self::PairsToMap, Self::ParseStringKeys, Self::SequenceToBytes
+    ];
+}
+
+
+/// The strategy to match map keys to struct fields and names to enum variants
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum NameMatching {
+    /// Names must match exactly
+    #[default]
+    Exact,
+    /// Names are matched case-insensitively (e.g. `userName` matches `USERNAME`)
+    CaseInsensitive,
+    /// Names are matched case-insensitively and across naming conventions (e.g. `user_name` matches `userName`,
+    /// `user-name` and `USER_NAME`)
+    NamingConvention
+}
+impl NameMatching {
+    /// Finds the candidate that matches `name` if `name` is not a candidate itself
+    ///
+    /// Returns an error if `name` matches multiple candidates.
+    pub fn find(&self, name: &str, candidates: &[&'static str]) -> Result<Option<&'static str>> {
+        if *self == Self::Exact || candidates.contains(&name) {
+            return Ok(None);
+        }
+
+        // Find the matching candidates
+        let normalized = self.normalize(name);
+        let mut matches = candidates.iter().filter(|candidate| self.normalize(candidate) == normalized);
+        match (matches.next(), matches.next()) {
+            (Some(first), Some(second)) => {
+                Err(einval!("The name {:?} matches both {:?} and {:?}", name, first, second))
+            },
+            (candidate, _) => Ok(candidate.copied())
+        }
+    }
+
+    /// Normalizes `name` for comparison
+    fn normalize(&self, name: &str) -> String {
+        match self {
+            Self::Exact => String::from(name),
+            Self::CaseInsensitive => name.to_lowercase(),
+            Self::NamingConvention => name.chars()
+                .filter(|char| !matches!(char, '_' | '-'))
+                .flat_map(char::to_lowercase)
+                .collect()
+        }
+    }
+}
+
+
+/// The deserialization options
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// The allowed coercions
+    coercions: BTreeSet<Coercion>,
+    /// Whether errors are collected or not
+    collect_errors: bool,
+    /// Whether the deserialization is strict or not
+    strict: bool,
+    /// The limits to enforce
+    limits: Limits,
+    /// Whether the deserializer announces a human readable format or not
+    human_readable: bool,
+    /// The accepted representation of Rust enums
+    enum_repr: EnumRepr,
+    /// The strategy to match field and variant names
+    name_matching: NameMatching,
+    /// Whether ignored or unvisited values are tracked or not
+    track_ignored: bool
+}
+impl Options {
+    /// Creates new default options which deserialize strictly typed
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Creates new options which allow all available coercions
+    pub fn lenient() -> Self {
+        Self { coercions: Coercion::ALL.iter().copied().collect(), ..Self::default() }
+    }
+
+    /// Allows `coercion` during deserialization
+    pub fn coerce(mut self, coercion: Coercion) -> Self {
+        self.coercions.insert(coercion);
+        self
+    }
+    /// Whether `coercion` is allowed or not
+    pub fn allows(&self, coercion: Coercion) -> bool {
+        self.coercions.contains(&coercion)
+    }
+
+    /// Sets whether values that cannot be converted should be recorded as error and substituted by the default value of
+    /// the requested type instead of failing
+    pub fn collect_errors(mut self, collect: bool) -> Self {
+        self.collect_errors = collect;
+        self
+    }
+    /// Whether errors are collected or not
+    pub fn collects_errors(&self) -> bool {
+        self.collect_errors
+    }
+
+    /// Sets whether sequences must have exactly the length of the requested tuple and whether all sequence elements and
+    /// map entries must be consumed by the visitor
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+    /// Whether the deserialization is strict or not
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Sets the limits to enforce during deserialization
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+    /// The limits to enforce during deserialization
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// Sets whether the deserializer announces a human readable format to the deserialized types or not (enabled by
+    /// default; must match the setting used during serialization)
+    pub fn human_readable(mut self, human_readable: bool) -> Self {
+        self.human_readable = human_readable;
+        self
+    }
+    /// Whether the deserializer announces a human readable format or not
+    pub fn is_human_readable(&self) -> bool {
+        self.human_readable
+    }
+
+    /// Sets the accepted representation of Rust enums
+    pub fn with_enum_repr(mut self, enum_repr: EnumRepr) -> Self {
+        self.enum_repr = enum_repr;
+        self
+    }
+    /// The accepted representation of Rust enums
+    pub fn enum_repr(&self) -> &EnumRepr {
+        &self.enum_repr
+    }
+
+    /// Sets the strategy to match map keys to struct fields and names to enum variants
+    pub fn with_name_matching(mut self, name_matching: NameMatching) -> Self {
+        self.name_matching = name_matching;
+        self
+    }
+    /// The strategy to match field and variant names
+    pub fn name_matching(&self) -> NameMatching {
+        self.name_matching
+    }
+
+    /// Sets whether the paths of values which are ignored by the visitor or never visited at all (e.g. unknown struct
+    /// fields) are recorded in the report
+    pub fn track_ignored(mut self, track: bool) -> Self {
+        self.track_ignored = track;
+        self
+    }
+    /// Whether ignored or unvisited values are tracked or not
+    pub fn tracks_ignored(&self) -> bool {
+        self.track_ignored
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            coercions: BTreeSet::new(), collect_errors: false, strict: false, limits: Limits::default(),
+            human_readable: true, enum_repr: EnumRepr::default(), name_matching: NameMatching::default(),
+            track_ignored: false
+        }
+    }
+}
+
+
+/// A report about a deserialization run
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    /// The applied coercions
+    pub(crate) coercions: Vec<Coercion>,
+    /// The collected errors
+    pub(crate) errors: Vec<Error>,
+    /// The paths of the ignored or unvisited values
+    pub(crate) ignored: Vec<Path>
+}
+impl Report {
+    /// The coercions that have been applied in the order of their application
+    pub fn coercions(&self) -> &[Coercion] {
+        &self.coercions
+    }
+    /// The errors that have been collected and replaced by default values in the order of their occurrence
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+    /// The paths of the values that have been ignored by the visitor or have never been visited in the order of their
+    /// occurrence (see `Options::track_ignored`)
+    pub fn ignored(&self) -> &[Path] {
+        &self.ignored
+    }
+}
@@ -0,0 +1,57 @@
+//! Implements a cheaply clonable path to the current deserialization location
+
+use crate::path::{ Path, Segment };
+use alloc::{ rc::Rc, vec::Vec };
+
+
+/// A trail node
+#[derive(Debug)]
+struct Node {
+    /// The parent trail
+    parent: Trail,
+    /// The last segment
+    segment: Segment,
+    /// The amount of segments from the root to this node
+    depth: usize
+}
+
+
+/// A cheaply clonable path from the root to the current deserialization location
+#[derive(Debug, Clone, Default)]
+pub struct Trail {
+    /// The last node or `None` if the trail points to the root
+    node: Option<Rc<Node>>
+}
+impl Trail {
+    /// Creates a new trail that points to the root
+    pub fn root() -> Self {
+        Self::default()
+    }
+    /// Creates a new trail by appending `segment` to `self`
+    pub fn child(&self, segment: Segment) -> Self {
+        let node = Node { parent: self.clone(), segment, depth: self.depth() + 1 };
+        Self { node: Some(Rc::new(node)) }
+    }
+
+    /// The last segment of the trail if any
+    pub fn segment(&self) -> Option<&Segment> {
+        self.node.as_ref().map(|node| &node.segment)
+    }
+    /// The amount of segments from the root to the current location
+    pub fn depth(&self) -> usize {
+        self.node.as_ref().map(|node| node.depth).unwrap_or_default()
+    }
+    /// Collects the trail into a path
+    pub fn to_path(&self) -> Path {
+        // Collect the segments from the end to the root
+        let mut segments = Vec::new();
+        let mut next = self.node.as_ref();
+        while let Some(node) = next {
+            segments.push(node.segment.clone());
+            next = node.parent.node.as_ref();
+        }
+
+        segments.reverse();
+        Path::from(segments)
+    }
+}
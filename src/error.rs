@@ -1,16 +1,40 @@
 //! A `serde_dynamic_typing` related error
 
-use ebacktrace::define_error;
+use crate::{
+    path::{ Path, Segment },
+    typing::{ AnyValue, Integer, Kind }
+};
+use alloc::{
+    borrow::Cow, boxed::Box,
+    string::{ String, ToString }
+};
+use core::{
+    any, result, ops::Deref,
+    fmt::{ self, Debug, Display, Formatter, Write }
+};
+#[cfg(feature = "std")]
 use std::{
-    error, result,
-    fmt::{ self, Display, Formatter }
+    error,
+    backtrace::{ Backtrace, BacktraceStatus }
 };
+#[cfg(feature = "backtrace")]
+use alloc::sync::Arc;
+// Reexport `format` for our macros so that they also work in `no_std` crates
+#[doc(hidden)]
+pub use alloc::format;
+
+
+/// The maximum length of a value preview in characters
+const PREVIEW_LEN: usize = 48;
+/// A disabled backtrace which is used if the `backtrace` feature is not enabled
+#[cfg(all(feature = "std", not(feature = "backtrace")))]
+static DISABLED_BACKTRACE: Backtrace = Backtrace::disabled();
 
 
 /// Creates a new variant
 #[doc(hidden)]
 #[macro_export] macro_rules! e {
-    ($kind:expr, $($arg:tt)*) => ({ $crate::error::ErrorImpl::with_string($kind, format!($($arg)*)) })
+    ($kind:expr, $($arg:tt)*) => ({ $crate::error::ErrorImpl::with_string($kind, $crate::error::format!($($arg)*)) })
 }
 /// Creates a new `Error::Serde` kind
 #[macro_export] macro_rules! eserde {
@@ -28,6 +52,14 @@ use std::{
 #[macro_export] macro_rules! enokey {
     ($($arg:tt)*) => ({ e!($crate::error::ErrorKind::NoSuchKey, $($arg)*) });
 }
+/// Creates a new `Error::LimitExceeded` kind
+#[macro_export] macro_rules! elimit {
+    ($($arg:tt)*) => ({ e!($crate::error::ErrorKind::LimitExceeded, $($arg)*) });
+}
+/// Creates a new `Error::TypeError` kind for a value that cannot be converted to the expected kind
+#[macro_export] macro_rules! emismatch {
+    ($expected:expr, $value:expr) => ({ $crate::error::ErrorImpl::type_mismatch($expected, &$value) });
+}
 
 
 /// A `serde_type_error` error kind
@@ -40,7 +72,9 @@ pub enum ErrorKind {
     /// Failed to convert a value from/to the given type
     TypeError,
     /// A specified key does not exist
-    NoSuchKey
+    NoSuchKey,
+    /// A configured limit has been exceeded
+    LimitExceeded
 }
 impl Display for ErrorKind {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
@@ -48,21 +82,246 @@ impl Display for ErrorKind {
             Self::SerdeError => write!(f, "Serde error"),
             Self::InvalidData => write!(f, "Invalid encoded data"),
             Self::TypeError => write!(f, "Failed to convert a value from/to the given type"),
-            Self::NoSuchKey => write!(f, "There is no such key or no value for the given key")
+            Self::NoSuchKey => write!(f, "There is no such key or no value for the given key"),
+            Self::LimitExceeded => write!(f, "A configured limit has been exceeded")
         }
     }
 }
+#[cfg(feature = "std")]
 impl error::Error for ErrorKind {
     /* Nothing to implement */
 }
+#[cfg(not(feature = "std"))]
+impl serde::de::StdError for ErrorKind {
+    /* Nothing to implement */
+}
+
+
+/// Structured details about an error
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Details {
+    /// A value has an unexpected kind
+    Type {
+        /// The expected kind
+        expected: Kind,
+        /// The kind of the value
+        found: Kind,
+        /// A short and possibly truncated preview of the value
+        preview: String
+    },
+    /// An integer is outside of the range of the target type
+    Range {
+        /// The integer value
+        value: Integer,
+        /// The smallest value of the target type
+        min: Integer,
+        /// The largest value of the target type
+        max: Integer,
+        /// The name of the target type
+        target: &'static str
+    }
+}
+
+
+/// A struct that wraps an error together with a backtrace, an optional description and an optional location
+pub struct ErrorImpl<E> {
+    /// The wrapped error
+    err: E,
+    /// The error description
+    desc: Cow<'static, str>,
+    /// Structured details about the error
+    details: Option<Box<Details>>,
+    /// The location within the simplified data structure where the error occurred
+    path: Option<Path>,
+    /// The backtrace
+    #[cfg(feature = "backtrace")]
+    backtrace: Arc<Backtrace>
+}
+impl<E> ErrorImpl<E> {
+    /// Wraps an error `err`
+    pub fn new(err: E) -> Self {
+        Self::with_cow(err, Cow::Borrowed(""))
+    }
+    /// Wraps an error `err` together with a description `desc`
+    pub fn with_str(err: E, desc: &'static str) -> Self {
+        Self::with_cow(err, Cow::Borrowed(desc))
+    }
+    /// Wraps an error `err` together with a description `desc`
+    pub fn with_string<S>(err: E, desc: S) -> Self where S: ToString {
+        Self::with_cow(err, Cow::Owned(desc.to_string()))
+    }
+    /// Wraps an error `err` together with a description `desc`
+    fn with_cow(err: E, desc: Cow<'static, str>) -> Self {
+        Self {
+            err, desc, details: None, path: None,
+            #[cfg(feature = "backtrace")]
+            backtrace: Arc::new(Backtrace::capture())
+        }
+    }
 
+    /// The wrapped error
+    pub const fn err(&self) -> &E {
+        &self.err
+    }
+    /// The error description
+    pub const fn desc(&self) -> &Cow<'static, str> {
+        &self.desc
+    }
+    /// Structured details about the error if available
+    pub fn details(&self) -> Option<&Details> {
+        self.details.as_deref()
+    }
+    /// The expected kind if the error is caused by a value of an unexpected kind
+    pub fn expected(&self) -> Option<Kind> {
+        match self.details() {
+            Some(Details::Type { expected, .. }) => Some(*expected),
+            _ => None
+        }
+    }
+    /// The kind of the offending value if the error is caused by a value of an unexpected kind
+    pub fn found(&self) -> Option<Kind> {
+        match self.details() {
+            Some(Details::Type { found, .. }) => Some(*found),
+            _ => None
+        }
+    }
+    /// A short and possibly truncated preview of the offending value if available
+    pub fn preview(&self) -> Option<&str> {
+        match self.details() {
+            Some(Details::Type { preview, .. }) => Some(preview),
+            _ => None
+        }
+    }
+    /// The location within the simplified data structure where the error occurred if the error occurred during
+    /// deserialization
+    pub const fn path(&self) -> Option<&Path> {
+        self.path.as_ref()
+    }
+    /// The backtrace
+    ///
+    /// Backtraces are only captured if the `backtrace` feature is enabled; otherwise this is always a disabled backtrace.
+    /// Like `Backtrace::capture`, the capturing also depends on the `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE` environment
+    /// variables.
+    #[cfg(feature = "std")]
+    pub fn backtrace(&self) -> &Backtrace {
+        #[cfg(feature = "backtrace")]
+        return self.backtrace.as_ref();
+        #[cfg(not(feature = "backtrace"))]
+        return &DISABLED_BACKTRACE;
+    }
 
-// Define our custom `serde_dynamic_typing` error
-define_error!(ErrorImpl);
+    /// Attaches structured `details` to the error
+    pub fn with_details(mut self, details: Details) -> Self {
+        self.details = Some(Box::new(details));
+        self
+    }
+    /// Prepends `segment` to the error location
+    pub(crate) fn prepend_path(mut self, segment: Segment) -> Self {
+        self.path.get_or_insert_with(Path::root).push_front(segment);
+        self
+    }
+    /// Sets the error location to `path`
+    pub(crate) fn with_path(mut self, path: Path) -> Self {
+        self.path = Some(path);
+        self
+    }
+    /// Sets the error location to the root if it is not set yet
+    pub(crate) fn rooted(mut self) -> Self {
+        self.path.get_or_insert_with(Path::root);
+        self
+    }
+}
+impl<E> Deref for ErrorImpl<E> {
+    type Target = E;
+    fn deref(&self) -> &Self::Target {
+        &self.err
+    }
+}
+impl<E> From<E> for ErrorImpl<E> {
+    fn from(error: E) -> Self {
+        Self::new(error)
+    }
+}
+#[cfg(feature = "std")]
+impl<E> error::Error for ErrorImpl<E> where E: error::Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.err.source()
+    }
+}
+#[cfg(not(feature = "std"))]
+impl<E> serde::de::StdError for ErrorImpl<E> where E: serde::de::StdError {
+    /* Nothing to implement */
+}
+impl<E> Debug for ErrorImpl<E> where E: Debug {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut debug = f.debug_struct(any::type_name::<Self>());
+        debug.field("err", &self.err)
+            .field("desc", &self.desc)
+            .field("details", &self.details)
+            .field("path", &self.path);
+        #[cfg(feature = "std")]
+        debug.field("backtrace", self.backtrace());
+        debug.finish()
+    }
+}
+impl<E> Display for ErrorImpl<E> where E: Display {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        // Write the error, description and location
+        write!(f, "{}", &self.err)?;
+        if !self.desc.is_empty() {
+            write!(f, " ({})", &self.desc)?;
+        }
+        if let Some(path) = self.path.as_ref() {
+            write!(f, " at {}", path)?;
+        }
+
+        // Print the backtrace if any
+        #[cfg(feature = "std")]
+        if self.backtrace().status() == BacktraceStatus::Captured {
+            writeln!(f)?;
+            writeln!(f, "{}", self.backtrace())?;
+        }
+        Ok(())
+    }
+}
+impl<E> Default for ErrorImpl<E> where E: Default {
+    fn default() -> Self {
+        Self::new(E::default())
+    }
+}
+impl<E> Clone for ErrorImpl<E> where E: Clone {
+    fn clone(&self) -> Self {
+        Self {
+            err: self.err.clone(),
+            desc: self.desc.clone(),
+            details: self.details.clone(),
+            path: self.path.clone(),
+            #[cfg(feature = "backtrace")]
+            backtrace: self.backtrace.clone()
+        }
+    }
+}
+impl ErrorImpl<ErrorKind> {
+    /// Creates a new `TypeError` for a `value` that cannot be converted to the `expected` kind
+    pub fn type_mismatch(expected: Kind, value: &AnyValue) -> Self {
+        let (found, preview) = (value.kind(), preview(value));
+        etype!("Cannot convert {} `{}` to {}", found, preview, expected)
+            .with_details(Details::Type { expected, found, preview })
+    }
+    /// Creates a new `TypeError` for an integer `value` that is outside of the range `min..=max` of `target`
+    pub fn out_of_range(value: Integer, min: Integer, max: Integer, target: &'static str) -> Self {
+        etype!("Cannot represent {} as `{}` (expected {}..={})", Numeric(value), target, Numeric(min), Numeric(max))
+            .with_details(Details::Range { value, min, max, target })
+    }
+}
 impl serde::de::Error for ErrorImpl<ErrorKind> {
     fn custom<T>(msg: T) -> Self where T: Display {
         Self::with_string(ErrorKind::SerdeError, msg.to_string())
     }
+    fn missing_field(field: &'static str) -> Self {
+        enokey!("Missing field `{}`", field).prepend_path(Segment::Field(String::from(field)))
+    }
 }
 impl serde::ser::Error for ErrorImpl<ErrorKind> {
     fn custom<T>(msg: T) -> Self where T: Display {
@@ -74,4 +333,84 @@ impl serde::ser::Error for ErrorImpl<ErrorKind> {
 /// A typealias for results with our error kind as specialization
 pub type Error = ErrorImpl<ErrorKind>;
 /// A typealias for results with our error as error-variant
-pub type Result<T = ()> = result::Result<T, ErrorImpl<ErrorKind>>;
\ No newline at end of file
+pub type Result<T = ()> = result::Result<T, ErrorImpl<ErrorKind>>;
+
+
+/// A helper to display integers by their numeric value
+struct Numeric(Integer);
+impl Display for Numeric {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.0 {
+            Integer::UnsignedInteger(value) => write!(f, "{}", value),
+            Integer::SignedInteger(value) => write!(f, "{}", value)
+        }
+    }
+}
+
+
+/// Creates a short and possibly truncated preview of `value`
+fn preview(value: &AnyValue) -> String {
+    // Note: writing into a `String` is infallible
+    let mut preview = String::new();
+    let _ = write_preview(value, &mut preview);
+
+    // Truncate the preview if necessary
+    if let Some((index, _)) = preview.char_indices().nth(PREVIEW_LEN) {
+        preview.truncate(index);
+        preview.push('…');
+    }
+    preview
+}
+/// Writes a preview of `value` to `buf`, stopping early once the preview is longer than `PREVIEW_LEN`
+fn write_preview(value: &AnyValue, buf: &mut String) -> fmt::Result {
+    if buf.len() > PREVIEW_LEN {
+        return Ok(());
+    }
+    match value {
+        AnyValue::Bool(value) => write!(buf, "{}", **value),
+        AnyValue::Integer(value) => write!(buf, "{}", Numeric(*value)),
+        AnyValue::Float(value) => write!(buf, "{:?}", **value),
+        AnyValue::Utf8String(value) => {
+            let truncated: String = value.chars().take(PREVIEW_LEN + 1).collect();
+            write!(buf, "{:?}", truncated)
+        },
+        AnyValue::Bytes(value) => write!(buf, "<{} bytes>", value.len()),
+        AnyValue::Sequence(sequence) => {
+            write!(buf, "[")?;
+            for (index, value) in sequence.iter().enumerate() {
+                if buf.len() > PREVIEW_LEN {
+                    break;
+                }
+                if index > 0 {
+                    write!(buf, ", ")?;
+                }
+                write_preview(value, buf)?;
+            }
+            write!(buf, "]")
+        },
+        AnyValue::Map(map) => {
+            write!(buf, "{{")?;
+            for (index, (key, value)) in map.iter().enumerate() {
+                if buf.len() > PREVIEW_LEN {
+                    break;
+                }
+                if index > 0 {
+                    write!(buf, ", ")?;
+                }
+                write_preview(key, buf)?;
+                write!(buf, ": ")?;
+                write_preview(value, buf)?;
+            }
+            write!(buf, "}}")
+        },
+        AnyValue::Enum(enumeration) => match enumeration.value() {
+            AnyValue::None => write!(buf, "{}", enumeration.variant()),
+            value => {
+                write!(buf, "{}(", enumeration.variant())?;
+                write_preview(value, buf)?;
+                write!(buf, ")")
+            }
+        },
+        AnyValue::None => write!(buf, "()")
+    }
+}
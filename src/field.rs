@@ -0,0 +1,137 @@
+//! Implements typed access to nested fields of Rust values
+
+use crate::{
+    de,
+    error::{ Error, ErrorKind, Result },
+    path::{ Path, Segment },
+    ser,
+    typing::{ AnyValue, Integer, Kind, Map, Utf8String }
+};
+use alloc::string::String;
+use core::mem;
+use serde::{ de::DeserializeOwned, Serialize };
+
+
+/// Reads the nested field at `field` (e.g. `"servers.2.port"`) of `value` as `F`
+///
+/// The field path consists of `.`-separated segments which select a map entry by its key, a sequence element by its
+/// index or the associated value of an enum by its variant name; an empty path selects `value` itself.
+///
+/// # Example
+/// ```
+/// # use serde_dynamic_typing::field;
+/// # use std::{ collections::BTreeMap, iter::FromIterator };
+/// let servers = BTreeMap::from_iter([(String::from("web"), vec![80u16, 443])]);
+/// let port: u16 = field::get_field(&servers, "web.1").expect("Failed to get field");
+/// assert_eq!(port, 443);
+/// ```
+pub fn get_field<T, F>(value: &T, field: &str) -> Result<F> where T: ?Sized + Serialize, F: DeserializeOwned {
+    let mut value = ser::to_typed(value)?;
+    let (field, path) = locate(&mut value, field)?;
+    de::from_typed(mem::replace(field, AnyValue::None)).map_err(|e| located(e, &path))
+}
+/// Replaces the nested field at `field` (see `get_field`) of `value` with `new`
+///
+/// If the field cannot be replaced or the resulting value is invalid, `value` is left untouched.
+pub fn set_field<T, F>(value: &mut T, field: &str, new: F) -> Result
+    where T: Serialize + DeserializeOwned, F: Serialize
+{
+    let mut updated = ser::to_typed(value)?;
+    let (field, _) = locate(&mut updated, field)?;
+    *field = ser::to_typed(&new)?;
+    *value = de::from_typed(updated)?;
+    Ok(())
+}
+
+
+/// Locates the nested field at `field` within `value` and returns it together with its path
+pub(crate) fn locate<'a>(value: &'a mut AnyValue, field: &str) -> Result<(&'a mut AnyValue, Path)> {
+    walk(value, field, false)
+}
+/// Sets the nested field at `field` within `value` to `new` and creates missing map entries (and maps in place of
+/// `AnyValue::None`) along the way
+pub(crate) fn insert(value: &mut AnyValue, field: &str, new: AnyValue) -> Result {
+    let (field, _) = walk(value, field, true)?;
+    *field = new;
+    Ok(())
+}
+/// Removes the nested map entry at `field` from `value` and returns its value if any
+pub(crate) fn take(value: &mut AnyValue, field: &str) -> Result<Option<AnyValue>> {
+    let (parent, name) = match field.rsplit_once('.') {
+        Some((parent, name)) => (parent, name),
+        None if !field.is_empty() => ("", field),
+        None => Err(einval!("Cannot take the root value"))?
+    };
+
+    // Locate the parent
+    let (parent, path) = match locate(value, parent) {
+        Ok(parent) => parent,
+        Err(e) if e.err() == &ErrorKind::NoSuchKey => return Ok(None),
+        Err(e) => Err(e)?
+    };
+    match parent {
+        AnyValue::Map(map) => {
+            let key = map_key(map, name);
+            Ok(map.remove(&key))
+        },
+        parent => Err(emismatch!(Kind::Map, parent).with_path(path))
+    }
+}
+
+
+/// Walks along the nested field at `field` within `value` and creates missing map entries if `create` is set
+fn walk<'a>(mut value: &'a mut AnyValue, field: &str, create: bool) -> Result<(&'a mut AnyValue, Path)> {
+    let mut path = Path::root();
+    for name in field.split('.').filter(|_| !field.is_empty()) {
+        if name.is_empty() {
+            Err(einval!("The field path {:?} contains an empty segment", field).with_path(path.clone()))?;
+        }
+        if create && *value == AnyValue::None {
+            *value = AnyValue::Map(Map::default());
+        }
+
+        // Select the child
+        value = match value {
+            AnyValue::Map(map) => {
+                let key = map_key(map, name);
+                path.push(Segment::Key(key.clone()));
+                match create {
+                    true => map.entry(key).or_insert(AnyValue::None),
+                    false => map.get_mut(&key)
+                        .ok_or_else(|| enokey!("Missing field `{}`", name).with_path(path.clone()))?
+                }
+            },
+            AnyValue::Sequence(sequence) => {
+                let index = name.parse().map_err(|_| {
+                    let error = etype!("Cannot select the field `{}` of a sequence (expected an index)", name);
+                    error.with_path(path.clone())
+                })?;
+                path.push(Segment::Index(index));
+                sequence.get_mut(index).ok_or_else(|| enokey!("Missing element `{}`", index).with_path(path.clone()))?
+            },
+            AnyValue::Enum(enumeration) => {
+                if enumeration.variant() != name {
+                    let error = enokey!("Missing variant `{}` (found `{}`)", name, enumeration.variant());
+                    Err(error.with_path(path.clone()))?;
+                }
+                path.push(Segment::Variant(String::from(name)));
+                enumeration.value_mut()
+            },
+            value => Err(emismatch!(Kind::Map, value).with_path(path.clone()))?
+        };
+    }
+    Ok((value, path))
+}
+/// Returns the key of `map` that is selected by the field segment `name`, which is an integer if `name` is a decimal
+/// number and `map` has no equivalent string key
+fn map_key(map: &Map, name: &str) -> AnyValue {
+    let key = AnyValue::Utf8String(Utf8String::from(name));
+    match name.parse::<u64>() {
+        Ok(integer) if !map.contains_key(&key) => AnyValue::Integer(Integer::from(integer)),
+        _ => key
+    }
+}
+/// Prepends `path` to the location of `error`
+fn located(error: Error, path: &Path) -> Error {
+    path.segments().iter().rev().fold(error, |error, segment| error.prepend_path(segment.clone()))
+}
@@ -64,15 +64,37 @@
 //!     )
 //! ])
 //! ```
+//!
+//!
+//! ## Features
+//! This crate builds on stable Rust and has the following feature gates:
+//!  - `std` (enabled by default): Implements `std::error::Error` for the error type; without this feature, the crate is
+//!    `#![no_std]` and only requires `alloc`
+//!  - `backtrace` (disabled by default, implies `std`): Captures a backtrace when an error is created (see
+//!    `Backtrace::capture` for the environment variables that control the capturing)
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
-#![feature(backtrace)]
 #[macro_use] pub mod error;
+pub mod path;
+pub mod limits;
+pub mod repr;
+pub mod tree;
 pub mod typing;
-mod ser;
-mod de;
+pub mod ser;
+pub mod de;
+pub mod partial;
+pub mod field;
+pub mod mapping;
+pub mod migration;
 
 // Reexport common symbols
 pub use crate::{
-    ser::to_typed, de::from_typed,
-    typing::{ AnyValue, Boolean, Bytes, Enumeration, Float, Integer, Map, Sequence, Utf8String }
+    ser::{ to_typed, to_typed_with },
+    de::{ from_typed, from_typed_collecting, from_typed_tracking, from_typed_with, from_typed_with_defaults },
+    field::{ get_field, set_field }, limits::Limits, mapping::Mapping, migration::Migrations,
+    partial::apply_partial, path::Path, repr::EnumRepr,
+    typing::{ AnyValue, Boolean, Bytes, Enumeration, Float, Integer, Kind, Map, Number, Sequence, Utf8String }
 };
\ No newline at end of file
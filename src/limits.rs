@@ -0,0 +1,83 @@
+//! Implements configurable limits to safely process untrusted data
+
+use crate::error::Result;
+
+
+/// Limits which are enforced during serialization and deserialization
+///
+/// All limits are disabled by default; use the builder methods to enable them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Limits {
+    /// The maximum nesting depth
+    max_depth: Option<usize>,
+    /// The maximum total amount of values
+    max_nodes: Option<usize>,
+    /// The maximum length of a string in bytes
+    max_string_len: Option<usize>,
+    /// The maximum length of a byte string
+    max_bytes_len: Option<usize>,
+    /// The maximum amount of elements of a sequence or entries of a map
+    max_collection_len: Option<usize>
+}
+impl Limits {
+    /// Creates new limits where all limits are disabled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum nesting depth where the root value has a depth of `0`
+    pub fn max_depth(mut self, max: usize) -> Self {
+        self.max_depth = Some(max);
+        self
+    }
+    /// Sets the maximum total amount of values including the root value and all nested values
+    pub fn max_nodes(mut self, max: usize) -> Self {
+        self.max_nodes = Some(max);
+        self
+    }
+    /// Sets the maximum length of a string in bytes
+    pub fn max_string_len(mut self, max: usize) -> Self {
+        self.max_string_len = Some(max);
+        self
+    }
+    /// Sets the maximum length of a byte string
+    pub fn max_bytes_len(mut self, max: usize) -> Self {
+        self.max_bytes_len = Some(max);
+        self
+    }
+    /// Sets the maximum amount of elements of a sequence or entries of a map
+    pub fn max_collection_len(mut self, max: usize) -> Self {
+        self.max_collection_len = Some(max);
+        self
+    }
+
+    /// Ensures that `depth` does not exceed the maximum nesting depth
+    pub(crate) fn check_depth(&self, depth: usize) -> Result {
+        check(self.max_depth, depth, "nesting depth")
+    }
+    /// Ensures that `nodes` does not exceed the maximum total amount of values
+    pub(crate) fn check_nodes(&self, nodes: usize) -> Result {
+        check(self.max_nodes, nodes, "total amount of values")
+    }
+    /// Ensures that `len` does not exceed the maximum string length
+    pub(crate) fn check_string_len(&self, len: usize) -> Result {
+        check(self.max_string_len, len, "string length")
+    }
+    /// Ensures that `len` does not exceed the maximum byte string length
+    pub(crate) fn check_bytes_len(&self, len: usize) -> Result {
+        check(self.max_bytes_len, len, "byte string length")
+    }
+    /// Ensures that `len` does not exceed the maximum collection length
+    pub(crate) fn check_collection_len(&self, len: usize) -> Result {
+        check(self.max_collection_len, len, "collection length")
+    }
+}
+
+
+/// Ensures that `value` does not exceed `max` if set
+fn check(max: Option<usize>, value: usize, name: &str) -> Result {
+    match max {
+        Some(max) if value > max => Err(elimit!("The {} of {} exceeds the limit of {}", name, value, max)),
+        _ => Ok(())
+    }
+}
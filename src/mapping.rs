@@ -0,0 +1,260 @@
+//! Implements declarative mappings between Rust types with similar structures
+
+use crate::{
+    de::{ self, Options },
+    error::{ ErrorKind, Result },
+    field,
+    path::{ Path, Segment },
+    ser,
+    typing::{ AnyValue, Map, Utf8String }
+};
+use alloc::{ boxed::Box, format, string::String, vec::Vec };
+use serde::{ de::DeserializeOwned, Serialize };
+
+
+/// A value computation
+type Compute = Box<dyn Fn(&AnyValue) -> Result<AnyValue> + Send + Sync>;
+
+
+/// A mapping rule
+enum Rule {
+    /// Moves the subtree at `from` to `to`
+    Move {
+        /// The source field path
+        from: String,
+        /// The target field path
+        to: String
+    },
+    /// Removes the field
+    Drop {
+        /// The field path
+        field: String
+    },
+    /// Sets the field to `value` if it is missing or `AnyValue::None`
+    Default {
+        /// The field path
+        field: String,
+        /// The default value
+        value: AnyValue
+    },
+    /// Renames the enum variants at the field
+    Variants {
+        /// The field path
+        field: String,
+        /// The old and new variant names
+        variants: Vec<(String, String)>
+    },
+    /// Sets the field to the value computed from the whole value
+    Compute {
+        /// The field path
+        field: String,
+        /// The computation
+        compute: Compute
+    }
+}
+
+
+/// A declarative mapping that converts a Rust value into another Rust type with a similar structure
+///
+/// The source value is converted into a simplified data structure, the rules are applied in the order of their
+/// definition and the result is deserialized into the target type. Field paths have the same format as in
+/// `field::get_field`.
+///
+/// # Example
+/// ```
+/// # use serde_dynamic_typing::{ mapping::Mapping, AnyValue, Utf8String };
+/// # use std::{ collections::BTreeMap, iter::FromIterator };
+/// let source = BTreeMap::from_iter([("hostname", "localhost")]);
+/// let mapping = Mapping::new()
+///     .rename("hostname", "host")
+///     .with_default("port", AnyValue::Utf8String(Utf8String::from("80")));
+///
+/// let target: BTreeMap<String, String> = mapping.convert(&source).expect("Failed to map value");
+/// assert_eq!(target["host"], "localhost");
+/// assert_eq!(target["port"], "80");
+/// ```
+#[derive(Default)]
+pub struct Mapping {
+    /// The rules in the order of their application
+    rules: Vec<Rule>
+}
+impl Mapping {
+    /// Creates a new empty mapping
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renames the field at `field` to `name` within the same parent
+    pub fn rename<F, N>(self, field: F, name: N) -> Self where F: Into<String>, N: AsRef<str> {
+        let from = field.into();
+        let to = match from.rsplit_once('.') {
+            Some((parent, _)) => format!("{}.{}", parent, name.as_ref()),
+            None => String::from(name.as_ref())
+        };
+        self.rule(Rule::Move { from, to })
+    }
+    /// Moves the subtree at `from` to `to` and creates missing parent maps if necessary
+    pub fn move_field<F, T>(self, from: F, to: T) -> Self where F: Into<String>, T: Into<String> {
+        self.rule(Rule::Move { from: from.into(), to: to.into() })
+    }
+    /// Removes the field at `field`
+    pub fn drop_field<F>(self, field: F) -> Self where F: Into<String> {
+        self.rule(Rule::Drop { field: field.into() })
+    }
+    /// Sets the field at `field` to `value` if it is missing or `AnyValue::None`
+    pub fn with_default<F>(self, field: F, value: AnyValue) -> Self where F: Into<String> {
+        self.rule(Rule::Default { field: field.into(), value })
+    }
+    /// Renames the variants of the enum (or of each enum within the sequence) at `field` according to the pairs of old
+    /// and new names in `variants`
+    pub fn rename_variants<F, I, O, N>(self, field: F, variants: I) -> Self
+        where F: Into<String>, I: IntoIterator<Item = (O, N)>, O: Into<String>, N: Into<String>
+    {
+        let variants = variants.into_iter().map(|(old, new)| (old.into(), new.into())).collect();
+        self.rule(Rule::Variants { field: field.into(), variants })
+    }
+    /// Sets the field at `field` to the result of `compute`, which is called with the whole value after the preceding
+    /// rules have been applied
+    pub fn compute<F, C>(self, field: F, compute: C) -> Self
+        where F: Into<String>, C: Fn(&AnyValue) -> Result<AnyValue> + Send + Sync + 'static
+    {
+        self.rule(Rule::Compute { field: field.into(), compute: Box::new(compute) })
+    }
+
+    /// Applies the rules to `value`
+    pub fn apply(&self, mut value: AnyValue) -> Result<AnyValue> {
+        for rule in self.rules.iter() {
+            match rule {
+                Rule::Move { from, to } => if let Some(subtree) = field::take(&mut value, from)? {
+                    field::insert(&mut value, to, subtree)?;
+                },
+                Rule::Drop { field } => {
+                    field::take(&mut value, field)?;
+                },
+                Rule::Default { field, value: default } => if is_missing(&mut value, field)? {
+                    field::insert(&mut value, field, default.clone())?;
+                },
+                Rule::Variants { field, variants } => if !is_missing(&mut value, field)? {
+                    let (target, _) = field::locate(&mut value, field)?;
+                    rename_variants(target, variants);
+                },
+                Rule::Compute { field, compute } => {
+                    let computed = compute(&value)?;
+                    field::insert(&mut value, field, computed)?;
+                }
+            }
+        }
+        Ok(value)
+    }
+    /// Converts `source` into `T` by applying the rules
+    pub fn convert<S, T>(&self, source: &S) -> Result<T> where S: ?Sized + Serialize, T: DeserializeOwned {
+        let value = self.apply(ser::to_typed(source)?)?;
+        de::from_typed(value)
+    }
+    /// Applies the rules to `source` and reports the fields that are not consumed by `T` and the fields that `T`
+    /// requires but which are missing, without failing at the first missing field
+    ///
+    /// # Note
+    /// Values that cannot be converted into the requested types are ignored since the report only covers the structure.
+    /// However, a missing enum cannot be substituted, so the dry-run fails if a required enum field is missing.
+    pub fn dry_run<S, T>(&self, source: &S) -> Result<Report> where S: ?Sized + Serialize, T: DeserializeOwned {
+        let mut value = self.apply(ser::to_typed(source)?)?;
+        let options = Options::new().collect_errors(true).track_ignored(true);
+
+        // Insert placeholders for the missing fields until the deserialization succeeds
+        let mut missing: Vec<Path> = Vec::new();
+        loop {
+            let error = match de::from_typed_with::<T>(value.clone(), &options) {
+                Ok((_, report)) => return Ok(Report { unmapped: report.ignored().to_vec(), missing }),
+                Err(e) if e.err() == &ErrorKind::NoSuchKey => e,
+                Err(e) => return Err(e)
+            };
+
+            // Record the missing field unless it is nested within another missing field
+            let path = error.path().cloned().unwrap_or_default();
+            if missing.contains(&path) || !insert_placeholder(&mut value, &path) {
+                return Err(error);
+            }
+            if !missing.iter().any(|parent| path.starts_with(parent)) {
+                missing.push(path);
+            }
+        }
+    }
+
+    /// Appends `rule`
+    fn rule(mut self, rule: Rule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+}
+
+
+/// The report of a mapping dry-run
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    /// The paths of the mapped fields that are not consumed by the target type
+    unmapped: Vec<Path>,
+    /// The paths of the fields that are required by the target type but missing
+    missing: Vec<Path>
+}
+impl Report {
+    /// The paths of the mapped fields that are not consumed by the target type
+    pub fn unmapped(&self) -> &[Path] {
+        &self.unmapped
+    }
+    /// The paths of the fields that are required by the target type but missing
+    pub fn missing(&self) -> &[Path] {
+        &self.missing
+    }
+    /// Whether all mapped fields are consumed and no required fields are missing
+    pub fn is_complete(&self) -> bool {
+        self.unmapped.is_empty() && self.missing.is_empty()
+    }
+}
+
+
+/// Whether the field at `field` within `value` is missing or `AnyValue::None`
+fn is_missing(value: &mut AnyValue, field: &str) -> Result<bool> {
+    match field::locate(value, field) {
+        Ok((value, _)) => Ok(*value == AnyValue::None),
+        Err(e) if e.err() == &ErrorKind::NoSuchKey => Ok(true),
+        Err(e) => Err(e)
+    }
+}
+/// Renames the variant of the enum `value` or of each enum within the sequence `value`
+fn rename_variants(value: &mut AnyValue, variants: &[(String, String)]) {
+    match value {
+        AnyValue::Enum(enumeration) => {
+            let renamed = variants.iter().find(|(old, _)| old == enumeration.variant());
+            if let Some((_, new)) = renamed {
+                *enumeration.variant_mut() = new.clone();
+            }
+        },
+        AnyValue::Sequence(sequence) => sequence.iter_mut().for_each(|value| rename_variants(value, variants)),
+        _ => ()
+    }
+}
+/// Inserts an `AnyValue::None` placeholder at `path` if the location can be created
+fn insert_placeholder(mut value: &mut AnyValue, path: &Path) -> bool {
+    for segment in path.segments() {
+        if *value == AnyValue::None {
+            *value = AnyValue::Map(Map::default());
+        }
+        value = match (value, segment) {
+            (AnyValue::Map(map), Segment::Key(key)) => map.entry(key.clone()).or_insert(AnyValue::None),
+            (AnyValue::Map(map), Segment::Field(name)) => {
+                let key = AnyValue::Utf8String(Utf8String::from(name.as_str()));
+                map.entry(key).or_insert(AnyValue::None)
+            },
+            (AnyValue::Sequence(sequence), Segment::Index(index)) => match sequence.get_mut(*index) {
+                Some(value) => value,
+                None => return false
+            },
+            (AnyValue::Enum(enumeration), Segment::Variant(variant)) if enumeration.variant() == variant => {
+                enumeration.value_mut()
+            },
+            _ => return false
+        };
+    }
+    true
+}
@@ -0,0 +1,159 @@
+//! Implements versioned migrations of simplified data structures
+
+use crate::{
+    de,
+    error::Result,
+    field,
+    typing::{ AnyValue, Enumeration, Integer }
+};
+use alloc::{ boxed::Box, collections::BTreeMap, string::String, vec::Vec };
+use core::convert::TryFrom;
+use serde::de::DeserializeOwned;
+
+
+/// A migration step
+type Step = Box<dyn Fn(AnyValue) -> Result<AnyValue> + Send + Sync>;
+
+
+/// A registry of migration steps that upgrade documents from one version to the next
+///
+/// # Example
+/// ```
+/// # use serde_dynamic_typing::{ migration::{ self, Migrations }, AnyValue, Integer, Map, Utf8String };
+/// # use std::iter::FromIterator;
+/// let key = |key: &str| AnyValue::Utf8String(Utf8String::from(key));
+/// let migrations = Migrations::new("version")
+///     .register(1, migration::rename_key("hostname", "host"));
+///
+/// let document = AnyValue::Map(Map::from_iter([
+///     (key("version"), AnyValue::Integer(Integer::from(1u8))),
+///     (key("hostname"), key("localhost"))
+/// ]));
+/// let migrated = migrations.migrate(document).expect("Failed to migrate document");
+/// assert_eq!(migrated, AnyValue::Map(Map::from_iter([
+///     (key("version"), AnyValue::Integer(Integer::from(2u8))),
+///     (key("host"), key("localhost"))
+/// ])));
+/// ```
+pub struct Migrations {
+    /// The field path of the document version
+    version_field: String,
+    /// The version of the first document format
+    base_version: u64,
+    /// The migration steps by the version they migrate from
+    steps: BTreeMap<u64, Step>
+}
+impl Migrations {
+    /// Creates a new empty registry for documents that store their version at `version_field` (see
+    /// `field::get_field` for the path format)
+    pub fn new<T>(version_field: T) -> Self where T: Into<String> {
+        Self { version_field: version_field.into(), base_version: 0, steps: BTreeMap::new() }
+    }
+
+    /// Sets the version of the first document format, which is the latest version as long as no steps are registered
+    /// (defaults to `0`)
+    pub fn with_base_version(mut self, version: u64) -> Self {
+        self.base_version = version;
+        self
+    }
+    /// The version of the first document format
+    pub fn base_version(&self) -> u64 {
+        self.base_version
+    }
+
+    /// Registers `step` which migrates a document from `version` to `version + 1`
+    ///
+    /// The step does not need to update the version field, this is done after each step.
+    ///
+    /// # Panics
+    /// Panics if `version` is `u64::MAX` since there is no version to migrate to.
+    pub fn register<F>(mut self, version: u64, step: F) -> Self
+        where F: Fn(AnyValue) -> Result<AnyValue> + Send + Sync + 'static
+    {
+        assert!(version < u64::MAX, "Cannot register a migration step from the largest possible version");
+        self.steps.insert(version, Box::new(step));
+        self
+    }
+    /// The latest document version, i.e. the version after the last registered step or the base version if no steps are
+    /// registered
+    pub fn latest(&self) -> u64 {
+        self.steps.keys().next_back().map_or(self.base_version, |version| version + 1)
+    }
+
+    /// Migrates `document` from the version stored in the document to the latest version
+    pub fn migrate(&self, mut document: AnyValue) -> Result<AnyValue> {
+        let (version, _) = field::locate(&mut document, &self.version_field)?;
+        let mut version = u64::try_from(Integer::try_from(version.clone())?)?;
+        let latest = self.latest();
+        if version > latest {
+            Err(einval!("The document version {} is newer than the latest version {}", version, latest))?;
+        }
+
+        // Apply the migration chain
+        while version < latest {
+            let step = self.steps.get(&version)
+                .ok_or_else(|| enokey!("Missing migration step from version {}", version))?;
+            document = step(document)?;
+            version += 1;
+            field::insert(&mut document, &self.version_field, AnyValue::Integer(Integer::from(version)))?;
+        }
+        Ok(document)
+    }
+    /// Migrates `document` to the latest version and deserializes it
+    pub fn migrate_and_deserialize<T>(&self, document: AnyValue) -> Result<T> where T: DeserializeOwned {
+        let document = self.migrate(document)?;
+        de::from_typed(document)
+    }
+}
+
+
+/// Creates a migration step that moves the field at `from` to `to` (see `field::get_field` for the path format)
+pub fn rename_key<F, T>(from: F, to: T) -> impl Fn(AnyValue) -> Result<AnyValue> + Send + Sync
+    where F: Into<String>, T: Into<String>
+{
+    let (from, to) = (from.into(), to.into());
+    move |mut document| {
+        if let Some(value) = field::take(&mut document, &from)? {
+            field::insert(&mut document, &to, value)?;
+        }
+        Ok(document)
+    }
+}
+/// Creates a migration step that wraps the field at `field` into an enum with the given `variant`
+pub fn wrap_enum<F, V>(field: F, variant: V) -> impl Fn(AnyValue) -> Result<AnyValue> + Send + Sync
+    where F: Into<String>, V: Into<String>
+{
+    let (field, variant) = (field.into(), variant.into());
+    move |mut document| {
+        if let Some(value) = field::take(&mut document, &field)? {
+            let enumeration = Enumeration::with_value(variant.as_str(), value);
+            field::insert(&mut document, &field, AnyValue::Enum(enumeration))?;
+        }
+        Ok(document)
+    }
+}
+/// Creates a migration step that removes the field at `field` and stores the values returned by `split` at the
+/// corresponding `targets`
+pub fn split_field<F, T, S>(field: F, targets: T, split: S) -> impl Fn(AnyValue) -> Result<AnyValue> + Send + Sync
+    where F: Into<String>, T: IntoIterator, T::Item: Into<String>,
+        S: Fn(AnyValue) -> Result<Vec<AnyValue>> + Send + Sync
+{
+    let field = field.into();
+    let targets: Vec<String> = targets.into_iter().map(Into::into).collect();
+    move |mut document| {
+        let value = match field::take(&mut document, &field)? {
+            Some(value) => value,
+            None => return Ok(document)
+        };
+
+        // Split the value and store the parts
+        let parts = split(value)?;
+        if parts.len() != targets.len() {
+            Err(einval!("Expected {} parts when splitting {}, but got {} parts", targets.len(), field, parts.len()))?;
+        }
+        for (target, part) in targets.iter().zip(parts) {
+            field::insert(&mut document, target, part)?;
+        }
+        Ok(document)
+    }
+}
@@ -0,0 +1,79 @@
+//! Implements partial updates of Rust values
+
+use crate::{
+    de,
+    error::Result,
+    path::Path,
+    ser,
+    tree,
+    typing::AnyValue
+};
+use alloc::vec::Vec;
+use serde::{ de::DeserializeOwned, Serialize };
+
+
+/// Applies the partial `update` to `target` and returns the paths of all values that have been changed
+///
+/// `target` is converted into a simplified data structure, `update` is deep-merged into it (see `tree::merge`) and the
+/// result is deserialized back into `target`. The changes are determined by comparing the re-serialized result against
+/// the original value, so entries that are ignored by `T` (e.g. unknown keys) are not reported. If the update fails,
+/// `target` is left untouched.
+///
+/// # Example
+/// ```
+/// # use serde_dynamic_typing::{ partial, AnyValue, Integer, Map, Utf8String };
+/// # use std::{ collections::BTreeMap, iter::FromIterator };
+/// let mut ports = BTreeMap::from_iter([(String::from("http"), 80u16), (String::from("https"), 443)]);
+/// let update = AnyValue::Map(Map::from_iter([
+///     (AnyValue::Utf8String(Utf8String::from("http")), AnyValue::Integer(Integer::from(8080u16)))
+/// ]));
+///
+/// let changed = partial::apply_partial(&mut ports, update).expect("Failed to apply update");
+/// assert_eq!(ports["http"], 8080);
+/// assert_eq!(changed[0].to_string(), "$.http");
+/// ```
+pub fn apply_partial<T>(target: &mut T, update: AnyValue) -> Result<Vec<Path>> where T: Serialize + DeserializeOwned {
+    let (original, merged) = match merge(target, update)? {
+        Some(values) => values,
+        None => return Ok(Vec::new())
+    };
+    let updated: T = de::from_typed(merged)?;
+    let changed = changes(original, &updated)?;
+    *target = updated;
+    Ok(changed)
+}
+/// Applies the partial `update` to `target` like `apply_partial`, but deserializes the merged value in place via
+/// `Deserialize::deserialize_in_place`
+///
+/// # Note
+/// This avoids the construction of a new value for types that support in-place deserialization (e.g. via
+/// `serde_derive`'s `deserialize_in_place` feature) and falls back to a regular deserialization otherwise. However,
+/// if the update fails, `target` may have been partially updated.
+pub fn apply_partial_in_place<T>(target: &mut T, update: AnyValue) -> Result<Vec<Path>>
+    where T: Serialize + DeserializeOwned
+{
+    let (original, merged) = match merge(target, update)? {
+        Some(values) => values,
+        None => return Ok(Vec::new())
+    };
+    de::from_typed_in_place(merged, target)?;
+    changes(original, target)
+}
+
+
+/// Converts `target` into a simplified data structure and merges `update` into it
+///
+/// Returns the original and the merged value or `None` if the update does not change anything.
+fn merge<T>(target: &T, update: AnyValue) -> Result<Option<(AnyValue, AnyValue)>> where T: Serialize {
+    let original = ser::to_typed(target)?;
+    let mut merged = original.clone();
+    match tree::merge(&mut merged, update).is_empty() {
+        true => Ok(None),
+        false => Ok(Some((original, merged)))
+    }
+}
+/// Converts `updated` into a simplified data structure and returns the paths of all values that differ from `original`
+fn changes<T>(mut original: AnyValue, updated: &T) -> Result<Vec<Path>> where T: Serialize {
+    let updated = ser::to_typed(updated)?;
+    Ok(tree::merge(&mut original, updated))
+}
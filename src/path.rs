@@ -0,0 +1,104 @@
+//! Implements paths which point to a location within a simplified data structure
+
+use crate::typing::{ AnyValue, Integer };
+use alloc::{ string::String, vec::Vec };
+use core::fmt::{ self, Display, Formatter };
+
+
+/// A single path segment
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Segment {
+    /// The index of a sequence element
+    Index(usize),
+    /// The key of a map entry
+    Key(AnyValue),
+    /// The name of a struct field as requested by the deserialized type
+    Field(String),
+    /// The associated value of an enum variant
+    Variant(String)
+}
+impl Display for Segment {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Index(index) => write!(f, "[{}]", index),
+            Self::Key(AnyValue::Utf8String(key)) if is_identifier(key) => write!(f, ".{}", key.as_str()),
+            Self::Key(AnyValue::Utf8String(key)) => write!(f, "[{:?}]", key.as_str()),
+            Self::Key(AnyValue::Integer(Integer::UnsignedInteger(key))) => write!(f, "[{}]", key),
+            Self::Key(AnyValue::Integer(Integer::SignedInteger(key))) => write!(f, "[{}]", key),
+            Self::Key(AnyValue::Bool(key)) => write!(f, "[{}]", **key),
+            Self::Key(key) => write!(f, "[{:?}]", key),
+            Self::Field(name) if is_identifier(name) => write!(f, ".{}", name),
+            Self::Field(name) => write!(f, "[{:?}]", name),
+            Self::Variant(variant) => write!(f, ".{}", variant)
+        }
+    }
+}
+
+
+/// A path to a location within a simplified data structure (e.g. `$.servers[2].port`)
+///
+/// # Note
+/// During deserialization, map entries that match a struct field requested by the deserialized type are recorded as
+/// `Segment::Field` with the requested field name (i.e. the name after `#[serde(rename)]` since serde does not expose
+/// the Rust identifiers); all other map entries are recorded as `Segment::Key`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Path {
+    /// The path segments from the root to the location
+    segments: Vec<Segment>
+}
+impl Path {
+    /// Creates a new path that points to the root
+    pub fn root() -> Self {
+        Self::default()
+    }
+
+    /// The path segments from the root to the location
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+    /// Whether the path points to the root or not
+    pub fn is_root(&self) -> bool {
+        self.segments.is_empty()
+    }
+    /// Whether the path points to `other` or to a location within `other`
+    pub fn starts_with(&self, other: &Self) -> bool {
+        self.segments.starts_with(&other.segments)
+    }
+
+    /// Appends `segment` to the end of the path
+    pub fn push(&mut self, segment: Segment) {
+        self.segments.push(segment);
+    }
+    /// Removes the last segment from the path if any
+    pub fn pop(&mut self) -> Option<Segment> {
+        self.segments.pop()
+    }
+    /// Prepends `segment` to the front of the path
+    pub fn push_front(&mut self, segment: Segment) {
+        self.segments.insert(0, segment);
+    }
+}
+impl From<Vec<Segment>> for Path {
+    fn from(segments: Vec<Segment>) -> Self {
+        Self { segments }
+    }
+}
+impl Display for Path {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "$")?;
+        for segment in self.segments.iter() {
+            write!(f, "{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+
+/// Whether `key` can be displayed in the `.key` notation or not
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    match chars.next() {
+        Some(first) if first.is_alphabetic() || first == '_' => chars.all(|c| c.is_alphanumeric() || c == '_'),
+        _ => false
+    }
+}
@@ -0,0 +1,151 @@
+//! Implements the selectable representations of Rust enums
+
+use crate::{
+    error::Result,
+    tree::rebuild,
+    typing::{ AnyValue, Enumeration, Kind, Map, Utf8String }
+};
+use alloc::string::String;
+use core::{ convert::TryFrom, iter::FromIterator, ops::ControlFlow };
+
+
+/// The representation of Rust enums within the simplified data structure
+///
+/// `External` represents unit variants as bare `"Variant"`-strings, while `Internal` and `Adjacent` represent them as
+/// tag-only maps like `{"type": "Variant"}`; during deserialization, bare strings and native enums are always accepted.
+///
+/// There is no untagged representation: the deserializer cannot determine the variant from the associated value alone,
+/// so untagged enums should use `#[serde(untagged)]` instead.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum EnumRepr {
+    /// Enums are represented as `AnyValue::Enum`
+    #[default]
+    Native,
+    /// Variants are represented as single-entry maps like `{"Variant": value}`
+    External,
+    /// Variants are represented as maps where the variant is stored under `tag` next to the fields of the associated
+    /// value like `{"type": "Variant", "field": value}`; the associated value must be a map
+    Internal {
+        /// The key of the variant
+        tag: String
+    },
+    /// Variants are represented as maps where the variant is stored under `tag` and the associated value is stored
+    /// under `content` like `{"t": "Variant", "c": value}`
+    Adjacent {
+        /// The key of the variant
+        tag: String,
+        /// The key of the associated value
+        content: String
+    }
+}
+impl EnumRepr {
+    /// Creates a new internally tagged representation
+    pub fn internal<T>(tag: T) -> Self where T: Into<String> {
+        Self::Internal { tag: tag.into() }
+    }
+    /// Creates a new adjacently tagged representation
+    pub fn adjacent<T, C>(tag: T, content: C) -> Self where T: Into<String>, C: Into<String> {
+        Self::Adjacent { tag: tag.into(), content: content.into() }
+    }
+
+    /// Encodes `enumeration` into this representation
+    pub fn encode(&self, enumeration: Enumeration) -> Result<AnyValue> {
+        let (variant, value) = enumeration.into_inner();
+        match (self, value) {
+            (Self::Native, value) => Ok(AnyValue::Enum(Enumeration::with_value(variant, value))),
+            (Self::External, AnyValue::None) => Ok(AnyValue::Utf8String(variant)),
+            (Self::External, value) => Ok(AnyValue::Map(Map::from_iter([(AnyValue::Utf8String(variant), value)]))),
+            (Self::Internal { tag }, AnyValue::None) => Ok(AnyValue::Map(Map::from_iter([
+                (AnyValue::Utf8String(Utf8String::from(tag.as_str())), AnyValue::Utf8String(variant))
+            ]))),
+            (Self::Internal { tag }, AnyValue::Map(mut map)) => {
+                let key = AnyValue::Utf8String(Utf8String::from(tag.as_str()));
+                if map.contains_key(&key) {
+                    Err(etype!("The enum tag {} conflicts with a field of the variant {}", tag, variant.as_str()))?;
+                }
+                map.insert(key, AnyValue::Utf8String(variant));
+                Ok(AnyValue::Map(map))
+            },
+            (Self::Internal { .. }, _) => {
+                Err(etype!("Cannot represent the variant {} without named fields as tagged map", variant.as_str()))
+            },
+            (Self::Adjacent { tag, .. }, AnyValue::None) => Ok(AnyValue::Map(Map::from_iter([
+                (AnyValue::Utf8String(Utf8String::from(tag.as_str())), AnyValue::Utf8String(variant))
+            ]))),
+            (Self::Adjacent { tag, content }, value) => Ok(AnyValue::Map(Map::from_iter([
+                (AnyValue::Utf8String(Utf8String::from(tag.as_str())), AnyValue::Utf8String(variant)),
+                (AnyValue::Utf8String(Utf8String::from(content.as_str())), value)
+            ])))
+        }
+    }
+    /// Decodes an enum in this representation from `value`
+    pub fn decode(&self, value: AnyValue) -> Result<Enumeration> {
+        match (self, value) {
+            (_, AnyValue::Enum(enumeration)) => Ok(enumeration),
+            (Self::Native, value) => Enumeration::try_from(value),
+            (_, AnyValue::Utf8String(variant)) => Ok(Enumeration::new(variant.as_str())),
+            (Self::External, AnyValue::Map(map)) if map.len() == 1 => {
+                let (variant, value) = map.into_iter().next().expect("Failed to get the single map entry?!");
+                let variant = Utf8String::try_from(variant)?;
+                Ok(Enumeration::with_value(variant.as_str(), value))
+            },
+            (Self::Internal { tag }, AnyValue::Map(mut map)) => {
+                let variant = take_variant(&mut map, tag)?;
+                match map.is_empty() {
+                    true => Ok(Enumeration::new(variant.as_str())),
+                    false => Ok(Enumeration::with_value(variant.as_str(), map))
+                }
+            },
+            (Self::Adjacent { tag, content }, AnyValue::Map(mut map)) => {
+                let variant = take_variant(&mut map, tag)?;
+                let value = map.remove(&AnyValue::Utf8String(Utf8String::from(content.as_str())));
+                if !map.is_empty() {
+                    Err(einval!("Adjacently tagged enum contains {} unexpected entries", map.len()))?;
+                }
+                Ok(Enumeration::with_value(variant.as_str(), value.unwrap_or(AnyValue::None)))
+            },
+            (_, value) => Err(emismatch!(Kind::Enum, value))
+        }
+    }
+}
+
+
+/// Converts all native enums within `value` into the representation `repr`
+pub fn encode_enums(value: AnyValue, repr: &EnumRepr) -> Result<AnyValue> {
+    rebuild(value, |value| Ok(ControlFlow::Continue(value)), |value| match value {
+        AnyValue::Enum(enumeration) => repr.encode(enumeration),
+        value => Ok(value)
+    })
+}
+/// Converts all values within `value` which are selected by `is_enum` from the representation `repr` into native enums
+///
+/// # Example
+/// ```
+/// # use serde_dynamic_typing::{ repr, AnyValue, EnumRepr, Map, Utf8String };
+/// # use std::iter::FromIterator;
+/// let value = AnyValue::Map(Map::from_iter([
+///     (AnyValue::Utf8String(Utf8String::from("type")), AnyValue::Utf8String(Utf8String::from("Empty")))
+/// ]));
+/// let repr = EnumRepr::internal("type");
+/// let decoded = repr::decode_enums(value, &repr, |value| matches!(value, AnyValue::Map(_))).expect("Invalid enum");
+/// assert!(matches!(decoded, AnyValue::Enum(_)));
+/// ```
+pub fn decode_enums<F>(value: AnyValue, repr: &EnumRepr, mut is_enum: F) -> Result<AnyValue>
+    where F: FnMut(&AnyValue) -> bool
+{
+    let decode = |value| match is_enum(&value) {
+        true => Ok(ControlFlow::Continue(AnyValue::Enum(repr.decode(value)?))),
+        false => Ok(ControlFlow::Continue(value))
+    };
+    rebuild(value, decode, Ok)
+}
+
+
+/// Removes the variant stored under `tag` from `map`
+fn take_variant(map: &mut Map, tag: &str) -> Result<Utf8String> {
+    match map.remove(&AnyValue::Utf8String(Utf8String::from(tag))) {
+        Some(variant) => Utf8String::try_from(variant),
+        None => Err(enokey!("Missing enum tag {}", tag))
+    }
+}
@@ -0,0 +1,45 @@
+//! Implements the shared serialization context
+
+use crate::{ error::Result, ser::options::Options };
+use core::cell::Cell;
+
+
+/// The context that is shared across all (sub-)serializers of a serialization run
+#[derive(Debug)]
+pub struct Context {
+    /// The serialization options
+    options: Options,
+    /// The amount of values that have been serialized so far including the root value
+    nodes: Cell<usize>,
+    /// Whether a `u8` has been serialized since the flag has been taken the last time
+    serialized_u8: Cell<bool>
+}
+impl Context {
+    /// Creates a new serialization context
+    pub fn new(options: Options) -> Self {
+        Self { options, nodes: Cell::new(1), serialized_u8: Cell::new(false) }
+    }
+
+    /// The serialization options
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    /// Records that a `u8` has been serialized
+    pub fn record_u8(&self) {
+        self.serialized_u8.set(true);
+    }
+    /// Takes the flag whether a `u8` has been serialized since the last call or not
+    pub fn take_u8(&self) -> bool {
+        self.serialized_u8.replace(false)
+    }
+
+    /// Registers a new value at the nesting level `depth` and enforces the depth and node limits
+    pub fn enter(&self, depth: usize) -> Result {
+        let nodes = self.nodes.get() + 1;
+        self.nodes.set(nodes);
+
+        self.options.limits().check_depth(depth)?;
+        self.options.limits().check_nodes(nodes)
+    }
+}
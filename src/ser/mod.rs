@@ -0,0 +1,458 @@
+//! Implements the serialization into the simplified data structure
+
+mod context;
+mod options;
+
+use crate::{
+    error::{ Result, Error },
+    limits::Limits,
+    repr::EnumRepr,
+    ser::context::Context,
+    typing::{ AnyValue, Bytes, Enumeration, Sequence, Map }
+};
+use serde::{
+    Serialize,
+    ser::{
+        self,
+        SerializeSeq, SerializeStruct, SerializeTuple, SerializeTupleStruct,
+        SerializeTupleVariant, SerializeMap, SerializeStructVariant
+    }
+};
+use alloc::{ boxed::Box, rc::Rc };
+use core::convert::TryFrom;
+// Reexport types
+pub use crate::ser::options::Options;
+
+
+/// A sequence writer
+pub struct SequenceWriter {
+    /// The elements to write
+    values: Sequence,
+    /// The announced number of elements if any
+    len: Option<usize>,
+    /// Whether all elements are `u8` or not
+    all_u8: bool,
+    /// The serializer of the sequence itself
+    serializer: Serializer,
+    /// An on-completion-handler
+    on_end: Box<dyn FnOnce(AnyValue) -> Result<AnyValue>>
+}
+impl SequenceWriter {
+    /// Creates a new sequence writer
+    fn new(len: Option<usize>, serializer: Serializer) -> Self {
+        Self::with_completion(len, serializer, Ok)
+    }
+    /// Creates a new sequence writer with a custom completion handler
+    fn with_completion<T>(len: Option<usize>, serializer: Serializer, on_end: T) -> Self
+        where T: FnOnce(AnyValue) -> Result<AnyValue> + 'static
+    {
+        let on_end = Box::new(on_end);
+        Self { values: Sequence::default(), len, all_u8: true, serializer, on_end }
+    }
+    
+    /// Writes the next `value` to the internal buffer
+    fn _serialize_element<T>(&mut self, value: &T) -> Result where T: ?Sized + Serialize {
+        self.serializer.limits().check_collection_len(self.values.len() + 1)?;
+        self.serializer.context.take_u8();
+        let value = value.serialize(self.serializer.child()?)?;
+
+        // Note: the flag is also set by nested `u8`s, so we also check that the element itself is an integer
+        self.all_u8 &= self.serializer.context.take_u8() && matches!(value, AnyValue::Integer(_));
+        self.values.push(value);
+        Ok(())
+    }
+    /// Finalizes the sequence
+    fn _end(self) -> Result<AnyValue> {
+        let options = self.serializer.options();
+        check_len(self.len, self.values.len(), options)?;
+        let list = match self.values {
+            values if options.collapses_bytes() && self.all_u8 && !values.is_empty() => {
+                options.limits().check_bytes_len(values.len())?;
+                AnyValue::Bytes(Bytes::try_from(values)?)
+            },
+            values => AnyValue::Sequence(values)
+        };
+        (self.on_end)(list)
+    }
+}
+impl SerializeSeq for SequenceWriter {
+    type Ok = AnyValue;
+    type Error = Error;
+    
+    fn serialize_element<T>(&mut self, value: &T) -> Result where T: ?Sized + Serialize {
+        self._serialize_element(value)
+    }
+    fn end(self) -> Result<Self::Ok> {
+        self._end()
+    }
+}
+impl SerializeTuple for SequenceWriter {
+    type Ok = AnyValue;
+    type Error = Error;
+    
+    fn serialize_element<T>(&mut self, value: &T) -> Result where T: ?Sized + Serialize {
+        self._serialize_element(value)
+    }
+    fn end(self) -> Result<Self::Ok> {
+        self._end()
+    }
+}
+impl SerializeTupleStruct for SequenceWriter {
+    type Ok = AnyValue;
+    type Error = Error;
+    
+    fn serialize_field<T>(&mut self, value: &T) -> Result where T: ?Sized + Serialize {
+        self._serialize_element(value)
+    }
+    fn end(self) -> Result<Self::Ok> {
+        self._end()
+    }
+}
+impl SerializeTupleVariant for SequenceWriter {
+    type Ok = AnyValue;
+    type Error = Error;
+    
+    fn serialize_field<T>(&mut self, value: &T) -> Result where T: ?Sized + Serialize {
+        self._serialize_element(value)
+    }
+    fn end(self) -> Result<Self::Ok> {
+        self._end()
+    }
+}
+
+
+/// A key-value writer
+pub struct KeyValueWriter {
+    /// The pending key to insert
+    pending_key: Option<AnyValue>,
+    /// The map
+    map: Map,
+    /// The announced number of entries if any
+    len: Option<usize>,
+    /// The serializer of the map itself
+    serializer: Serializer,
+    /// An on-completion-handler
+    on_end: Box<dyn FnOnce(AnyValue) -> Result<AnyValue>>
+}
+impl KeyValueWriter {
+    /// Creates a new key-value writer
+    fn new(len: Option<usize>, serializer: Serializer) -> Self {
+        Self::with_completion(len, serializer, Ok)
+    }
+    /// Creates a new key-value writer with a custom completion handler
+    fn with_completion<T>(len: Option<usize>, serializer: Serializer, on_end: T) -> Self
+        where T: FnOnce(AnyValue) -> Result<AnyValue> + 'static
+    {
+        let on_end = Box::new(on_end);
+        Self { pending_key: None, map: Map::default(), len, serializer, on_end }
+    }
+    
+    /// Writes the next `key` to the internal buffer
+    fn _serialize_key<T>(&mut self, key: &T) -> Result where T: ?Sized + Serialize {
+        self.ensure_no_pending_key()?;
+        self.serializer.limits().check_collection_len(self.map.len() + 1)?;
+        self.pending_key = Some(key.serialize(self.serializer.child()?)?);
+        Ok(())
+    }
+    /// Writes the associated `value` to the pending key
+    fn _serialize_value<T>(&mut self, value: &T) -> Result where T: ?Sized + Serialize {
+        let key = self.pending_key.take()
+            .ok_or_else(|| eserde!("Cannot serialize a map value without a preceding key"))?;
+        self.map.insert(key, value.serialize(self.serializer.child()?)?);
+        Ok(())
+    }
+    /// Writes the next `key` together with the associated `value` to the internal buffer
+    fn _serialize_entry<K, V>(&mut self, key: &K, value: &V) -> Result
+        where K: ?Sized + Serialize, V: ?Sized + Serialize
+    {
+        self._serialize_key(key)?;
+        self._serialize_value(value)
+    }
+    /// Ensures that there is no pending key without an associated value
+    fn ensure_no_pending_key(&self) -> Result {
+        if self.pending_key.is_some() {
+            Err(eserde!("There is a pending map key without an associated value"))?;
+        }
+        Ok(())
+    }
+    
+    /// Finalizes the sequence
+    fn _end(self) -> Result<AnyValue> {
+        self.ensure_no_pending_key()?;
+        check_len(self.len, self.map.len(), self.serializer.context.options())?;
+        let map = AnyValue::Map(self.map);
+        (self.on_end)(map)
+    }
+}
+impl SerializeMap for KeyValueWriter {
+    type Ok = AnyValue;
+    type Error = Error;
+    
+    fn serialize_key<T>(&mut self, key: &T) -> Result where T: ?Sized + Serialize {
+        self._serialize_key(key)
+    }
+    fn serialize_value<T>(&mut self, value: &T) -> Result where T: ?Sized + Serialize {
+        self._serialize_value(value)
+    }
+    fn serialize_entry<K, V>(&mut self, key: &K, value: &V) -> Result
+        where K: ?Sized + Serialize, V: ?Sized + Serialize
+    {
+        self._serialize_entry(key, value)
+    }
+    fn end(self) -> Result<Self::Ok> {
+        self._end()
+    }
+}
+impl SerializeStructVariant for KeyValueWriter {
+    type Ok = AnyValue;
+    type Error = Error;
+    
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result where T: ?Sized + Serialize {
+        self._serialize_entry(key, value)
+    }
+    fn end(self) -> Result<Self::Ok> {
+        self._end()
+    }
+}
+impl SerializeStruct for KeyValueWriter {
+    type Ok = AnyValue;
+    type Error = Error;
+    
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result where T: ?Sized + Serialize {
+        self._serialize_entry(key, value)
+    }
+    fn end(self) -> Result<Self::Ok> {
+        self._end()
+    }
+}
+
+
+/// A serializer for `AnyValue`
+///
+/// # Example
+/// ```
+/// # use serde::Serialize;
+/// # use serde_dynamic_typing::{ ser::Serializer, AnyValue, EnumRepr, Limits };
+/// let serializer = Serializer::new().with_limits(Limits::new().max_depth(16)).with_enum_repr(EnumRepr::External);
+/// let value = Some(7u8).serialize(serializer).expect("Failed to serialize value");
+/// assert!(matches!(value, AnyValue::Integer(_)));
+/// ```
+pub struct Serializer {
+    /// The serialization context
+    context: Rc<Context>,
+    /// The nesting depth of the value to serialize
+    depth: usize
+}
+impl Serializer {
+    /// Creates a new serializer with the default options
+    pub fn new() -> Self {
+        Self::with_options(Options::default())
+    }
+    /// Creates a new serializer with the given options
+    pub fn with_options(options: Options) -> Self {
+        Self { context: Rc::new(Context::new(options)), depth: 0 }
+    }
+
+    /// Sets whether the serializer announces a human readable format to the serialized types or not
+    pub fn human_readable(self, human_readable: bool) -> Self {
+        self.configure(|options| options.human_readable(human_readable))
+    }
+    /// Sets the limits to enforce during serialization
+    pub fn with_limits(self, limits: Limits) -> Self {
+        self.configure(|options| options.with_limits(limits))
+    }
+    /// Sets the representation of Rust enums
+    pub fn with_enum_repr(self, enum_repr: EnumRepr) -> Self {
+        self.configure(|options| options.with_enum_repr(enum_repr))
+    }
+    /// The serialization options
+    pub fn options(&self) -> &Options {
+        self.context.options()
+    }
+
+    /// Replaces the options with `configure(options)`
+    fn configure<F>(self, configure: F) -> Self where F: FnOnce(Options) -> Options {
+        let options = configure(self.options().clone());
+        Self { context: Rc::new(Context::new(options)), depth: self.depth }
+    }
+    /// Creates a new serializer instance for a value that is nested within the current value
+    fn child(&self) -> Result<Self> {
+        let depth = self.depth + 1;
+        self.context.enter(depth)?;
+        Ok(Self { context: self.context.clone(), depth })
+    }
+    /// The limits to enforce
+    fn limits(&self) -> &Limits {
+        self.options().limits()
+    }
+
+    /// Encodes `variant` with the associated `value` according to the configured enum representation
+    fn variant(&self, variant: &'static str, value: AnyValue) -> Result<AnyValue> {
+        self.options().enum_repr().encode(Enumeration::with_value(variant, value))
+    }
+}
+impl Default for Serializer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl ser::Serializer for Serializer {
+    type Ok = AnyValue;
+    type Error = Error;
+    
+    type SerializeSeq = SequenceWriter;
+    type SerializeTuple = SequenceWriter;
+    type SerializeTupleStruct = SequenceWriter;
+    type SerializeTupleVariant = SequenceWriter;
+    type SerializeMap = KeyValueWriter;
+    type SerializeStruct = KeyValueWriter;
+    type SerializeStructVariant = KeyValueWriter;
+    
+    fn is_human_readable(&self) -> bool {
+        self.options().is_human_readable()
+    }
+
+    fn serialize_bool(self, value: bool) -> Result<Self::Ok> {
+        Ok(AnyValue::Bool(value.into()))
+    }
+    
+    fn serialize_i8(self, value: i8) -> Result<Self::Ok> {
+        Ok(AnyValue::Integer(value.into()))
+    }
+    fn serialize_i16(self, value: i16) -> Result<Self::Ok> {
+        Ok(AnyValue::Integer(value.into()))
+    }
+    fn serialize_i32(self, value: i32) -> Result<Self::Ok> {
+        Ok(AnyValue::Integer(value.into()))
+    }
+    fn serialize_i64(self, value: i64) -> Result<Self::Ok> {
+        Ok(AnyValue::Integer(value.into()))
+    }
+    fn serialize_i128(self, value: i128) -> Result<Self::Ok> {
+        Ok(AnyValue::Integer(value.into()))
+    }
+    
+    fn serialize_u8(self, value: u8) -> Result<Self::Ok> {
+        self.context.record_u8();
+        Ok(AnyValue::Integer(value.into()))
+    }
+    fn serialize_u16(self, value: u16) -> Result<Self::Ok> {
+        Ok(AnyValue::Integer(value.into()))
+    }
+    fn serialize_u32(self, value: u32) -> Result<Self::Ok> {
+        Ok(AnyValue::Integer(value.into()))
+    }
+    fn serialize_u64(self, value: u64) -> Result<Self::Ok> {
+        Ok(AnyValue::Integer(value.into()))
+    }
+    fn serialize_u128(self, value: u128) -> Result<Self::Ok> {
+        Ok(AnyValue::Integer(value.into()))
+    }
+    
+    fn serialize_f32(self, value: f32) -> Result<Self::Ok> {
+        Ok(AnyValue::Float(value.into()))
+    }
+    fn serialize_f64(self, value: f64) -> Result<Self::Ok> {
+        Ok(AnyValue::Float(value.into()))
+    }
+    
+    fn serialize_char(self, value: char) -> Result<Self::Ok> {
+        self.limits().check_string_len(value.len_utf8())?;
+        Ok(AnyValue::Utf8String(value.into()))
+    }
+    fn serialize_str(self, value: &str) -> Result<Self::Ok> {
+        self.limits().check_string_len(value.len())?;
+        Ok(AnyValue::Utf8String(value.into()))
+    }
+    
+    fn serialize_bytes(self, value: &[u8]) -> Result<Self::Ok> {
+        self.limits().check_bytes_len(value.len())?;
+        Ok(AnyValue::Bytes(value.into()))
+    }
+    
+    fn serialize_none(self) -> Result<Self::Ok> {
+        Ok(AnyValue::None)
+    }
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok> where T: ?Sized + Serialize {
+        value.serialize(self)
+    }
+    
+    fn serialize_unit(self) -> Result<Self::Ok> {
+        Ok(AnyValue::None)
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok> {
+        Ok(AnyValue::None)
+    }
+    
+    fn serialize_unit_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str)
+        -> Result<Self::Ok>
+    {
+        self.variant(variant, AnyValue::None)
+    }
+    
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Self::Ok>
+        where T: ?Sized + Serialize
+    {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T>(self, _name: &'static str, _variant_index: u32, variant: &'static str, value: &T)
+        -> Result<Self::Ok> where T: ?Sized + Serialize
+    {
+        let value = value.serialize(self.child()?)?;
+        self.variant(variant, value)
+    }
+    
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        // Note: the length of a sequence is only a hint, so we don't check it
+        Ok(SequenceWriter::new(None, self))
+    }
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
+        Ok(SequenceWriter::new(Some(len), self))
+    }
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeTupleStruct> {
+        Ok(SequenceWriter::new(Some(len), self))
+    }
+    fn serialize_tuple_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str, len: usize)
+        -> Result<Self::SerializeTupleVariant>
+    {
+        let child = self.child()?;
+        let on_end = move |value| self.variant(variant, value);
+        Ok(SequenceWriter::with_completion(Some(len), child, on_end))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        // Note: the length of a map is only a hint, so we don't check it
+        Ok(KeyValueWriter::new(None, self))
+    }
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
+        Ok(KeyValueWriter::new(Some(len), self))
+    }
+    fn serialize_struct_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str, len: usize)
+        -> Result<Self::SerializeStructVariant>
+    {
+        let child = self.child()?;
+        let on_end = move |value| self.variant(variant, value);
+        Ok(KeyValueWriter::with_completion(Some(len), child, on_end))
+    }
+}
+
+
+/// Ensures in strict mode that the `written` number of elements matches the announced length `len` if any
+fn check_len(len: Option<usize>, written: usize, options: &Options) -> Result {
+    match len {
+        Some(len) if options.is_strict() && len != written => {
+            Err(eserde!("Announced {} elements, but {} elements have been serialized", len, written))
+        },
+        _ => Ok(())
+    }
+}
+
+
+/// Serializes a Rust value into a simplified data structure
+pub fn to_typed<T>(value: &T) -> Result<AnyValue> where T: ?Sized + Serialize {
+    to_typed_with(value, &Options::default())
+}
+/// Serializes a Rust value into a simplified data structure using the given options
+pub fn to_typed_with<T>(value: &T, options: &Options) -> Result<AnyValue> where T: ?Sized + Serialize {
+    value.serialize(Serializer::with_options(options.clone()))
+}
@@ -0,0 +1,86 @@
+//! Implements the configuration of a serialization run
+
+use crate::{ limits::Limits, repr::EnumRepr };
+
+
+/// The serialization options
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// Whether the serialization is strict or not
+    strict: bool,
+    /// The limits to enforce
+    limits: Limits,
+    /// Whether the serializer announces a human readable format or not
+    human_readable: bool,
+    /// The representation of Rust enums
+    enum_repr: EnumRepr,
+    /// Whether sequences of `u8` are collapsed into bytes or not
+    collapse_bytes: bool
+}
+impl Options {
+    /// Creates new default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the announced lengths of tuples and structs must match the number of serialized elements or fields
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+    /// Whether the serialization is strict or not
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Sets the limits to enforce during serialization
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+    /// The limits to enforce during serialization
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// Sets whether the serializer announces a human readable format to the serialized types or not (enabled by default;
+    /// must match the setting used during deserialization)
+    pub fn human_readable(mut self, human_readable: bool) -> Self {
+        self.human_readable = human_readable;
+        self
+    }
+    /// Whether the serializer announces a human readable format or not
+    pub fn is_human_readable(&self) -> bool {
+        self.human_readable
+    }
+
+    /// Sets the representation of Rust enums
+    pub fn with_enum_repr(mut self, enum_repr: EnumRepr) -> Self {
+        self.enum_repr = enum_repr;
+        self
+    }
+    /// The representation of Rust enums
+    pub fn enum_repr(&self) -> &EnumRepr {
+        &self.enum_repr
+    }
+
+    /// Sets whether non-empty sequences of `u8` (e.g. a plain `Vec<u8>`) are collapsed into `AnyValue::Bytes` or not
+    ///
+    /// See `tree::collapse_bytes` for the limitations of collapsed bytes during deserialization.
+    pub fn collapse_bytes(mut self, collapse: bool) -> Self {
+        self.collapse_bytes = collapse;
+        self
+    }
+    /// Whether sequences of `u8` are collapsed into bytes or not
+    pub fn collapses_bytes(&self) -> bool {
+        self.collapse_bytes
+    }
+}
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            strict: false, limits: Limits::default(), human_readable: true, enum_repr: EnumRepr::default(),
+            collapse_bytes: false
+        }
+    }
+}
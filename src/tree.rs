@@ -0,0 +1,320 @@
+//! Implements transformations of whole simplified data structures
+
+use crate::{
+    error::Result,
+    path::{ Path, Segment },
+    typing::{ AnyValue, Bytes, Enumeration, Integer, Map, Sequence, Utf8String }
+};
+use alloc::{ string::String, vec, vec::Vec };
+use core::{ convert::{ Infallible, TryFrom }, iter::FromIterator, ops::ControlFlow, result };
+
+
+/// Recursively converts all non-empty sequences whose elements are all integers within `0..=255` into bytes
+///
+/// # Note
+/// Integers do not record the width of their original type, so this also converts sequences of wider integer types
+/// (e.g. a `Vec<u16>`) if all elements fit into a `u8`. The deserializer reads bytes wherever a sequence of integers is
+/// requested, but self-describing visitors (e.g. for `serde(flatten)` or untagged enums) receive them as byte buffers,
+/// which cannot be read back as sequences; so collapsed sequences within such values only deserialize into byte
+/// containers like `serde_bytes::ByteBuf`.
+pub fn collapse_bytes(value: AnyValue) -> AnyValue {
+    let collapsed: result::Result<_, Infallible> = rebuild(value, |value| match value {
+        AnyValue::Sequence(sequence) if is_byte_sequence(&sequence) => {
+            let bytes = Bytes::try_from(sequence).expect("Failed to convert a byte sequence into bytes?!");
+            Ok(ControlFlow::Break(AnyValue::Bytes(bytes)))
+        },
+        value => Ok(ControlFlow::Continue(value))
+    }, Ok);
+    match collapsed {
+        Ok(value) => value,
+        Err(never) => match never {}
+    }
+}
+
+/// Recursively converts all integer, float and boolean map keys into strings (e.g. to export the value to formats like
+/// JSON which only support string keys)
+///
+/// # Note
+/// Keys of other types are rejected, as are maps where different keys have the same string representation.
+pub fn stringify_keys(value: AnyValue) -> Result<AnyValue> {
+    rebuild(value, |value| Ok(ControlFlow::Continue(value)), |value| match value {
+        AnyValue::Map(entries) => {
+            let mut map = Map::default();
+            for (key, value) in entries {
+                let key = stringify_key(key)?;
+                if map.contains_key(&key) {
+                    Err(einval!("The map contains multiple keys with the string representation {:?}", key))?;
+                }
+                map.insert(key, value);
+            }
+            Ok(AnyValue::Map(map))
+        },
+        value => Ok(value)
+    })
+}
+
+/// Recursively merges `update` into `target` and returns the paths of all values that have been changed
+///
+/// Maps are merged entry by entry and the associated values of enums with the same variant are merged; all other values
+/// in `update` (including sequences) replace the corresponding values in `target`.
+///
+/// # Example
+/// ```
+/// # use serde_dynamic_typing::{ tree, AnyValue, Integer, Map, Utf8String };
+/// # use std::iter::FromIterator;
+/// let key = |key: &str| AnyValue::Utf8String(Utf8String::from(key));
+/// let mut target = AnyValue::Map(Map::from_iter([
+///     (key("host"), key("localhost")),
+///     (key("port"), AnyValue::Integer(Integer::from(80u8)))
+/// ]));
+/// let update = AnyValue::Map(Map::from_iter([(key("port"), AnyValue::Integer(Integer::from(443u16)))]));
+///
+/// let changed = tree::merge(&mut target, update);
+/// assert_eq!(changed.len(), 1);
+/// assert_eq!(changed[0].to_string(), "$.port");
+/// ```
+pub fn merge(target: &mut AnyValue, update: AnyValue) -> Vec<Path> {
+    let (mut path, mut changed) = (Path::root(), Vec::new());
+    let mut steps = vec![MergeStep::Merge(target, update, None)];
+    while let Some(step) = steps.pop() {
+        let (target, update) = match step {
+            MergeStep::Merge(target, update, segment) => {
+                // Enter the location of the merged value and leave it again after all nested steps
+                if let Some(segment) = segment {
+                    path.push(segment);
+                    steps.push(MergeStep::Leave);
+                }
+                (target, update)
+            },
+            MergeStep::Inserted(segment) => {
+                path.push(segment);
+                changed.push(path.clone());
+                path.pop();
+                continue;
+            },
+            MergeStep::Leave => {
+                path.pop();
+                continue;
+            }
+        };
+
+        // Note: the nested steps are pushed in reverse order so that they are processed in order
+        let start = steps.len();
+        match (target, update) {
+            (AnyValue::Map(target), AnyValue::Map(update)) => {
+                // Insert the new entries first so that all existing entries can be borrowed at once afterwards
+                let mut entries = Vec::new();
+                for (key, value) in update {
+                    match target.contains_key(&key) {
+                        true => entries.push((key, Some(value))),
+                        false => {
+                            target.insert(key.clone(), value);
+                            entries.push((key, None));
+                        }
+                    }
+                }
+                let mut targets = target.iter_mut();
+                for (key, value) in entries {
+                    match value {
+                        Some(value) => {
+                            let (_, target) = targets.find(|(candidate, _)| **candidate == key)
+                                .expect("Failed to find an existing map entry?!");
+                            steps.push(MergeStep::Merge(target, value, Some(Segment::Key(key))));
+                        },
+                        None => steps.push(MergeStep::Inserted(Segment::Key(key)))
+                    }
+                }
+            },
+            (AnyValue::Enum(target), AnyValue::Enum(update)) => match target.variant() == update.variant() {
+                true => {
+                    let (variant, value) = update.into_inner();
+                    let segment = Segment::Variant(String::from(variant));
+                    steps.push(MergeStep::Merge(target.value_mut(), value, Some(segment)));
+                },
+                false => {
+                    *target = update;
+                    changed.push(path.clone());
+                }
+            },
+            (target, update) => if *target != update {
+                *target = update;
+                changed.push(path.clone());
+            }
+        }
+        steps[start..].reverse();
+    }
+    changed
+}
+/// Recursively fills missing map entries and `AnyValue::None` values within `value` with the corresponding values from
+/// `defaults`
+///
+/// Maps are filled entry by entry and the associated values of enums with the same variant are filled; all other values
+/// (including sequences) are taken from `value` as they are.
+///
+/// # Example
+/// ```
+/// # use serde_dynamic_typing::{ tree, AnyValue, Integer, Map, Utf8String };
+/// # use std::iter::FromIterator;
+/// let key = |key: &str| AnyValue::Utf8String(Utf8String::from(key));
+/// let value = AnyValue::Map(Map::from_iter([(key("host"), key("example.org")), (key("port"), AnyValue::None)]));
+/// let defaults = AnyValue::Map(Map::from_iter([
+///     (key("host"), key("localhost")),
+///     (key("port"), AnyValue::Integer(Integer::from(80u8)))
+/// ]));
+///
+/// let filled = tree::fill_defaults(value, &defaults);
+/// assert_eq!(filled, AnyValue::Map(Map::from_iter([
+///     (key("host"), key("example.org")),
+///     (key("port"), AnyValue::Integer(Integer::from(80u8)))
+/// ])));
+/// ```
+pub fn fill_defaults(mut value: AnyValue, defaults: &AnyValue) -> AnyValue {
+    let mut pending = vec![(&mut value, defaults)];
+    while let Some((value, defaults)) = pending.pop() {
+        match (value, defaults) {
+            (AnyValue::Map(map), AnyValue::Map(defaults)) => {
+                // Insert the missing entries first so that all entries can be borrowed at once afterwards
+                for key in defaults.keys() {
+                    if !map.contains_key(key) {
+                        map.insert(key.clone(), AnyValue::None);
+                    }
+                }
+                let mut values = map.iter_mut();
+                for (key, default) in defaults.iter() {
+                    let (_, value) = values.find(|(candidate, _)| *candidate == key)
+                        .expect("Failed to find a filled map entry?!");
+                    pending.push((value, default));
+                }
+            },
+            (AnyValue::Enum(enumeration), AnyValue::Enum(defaults)) => {
+                if enumeration.variant() == defaults.variant() {
+                    pending.push((enumeration.value_mut(), defaults.value()));
+                }
+            },
+            (value, defaults) => if let AnyValue::None = value {
+                *value = defaults.clone();
+            }
+        }
+    }
+    value
+}
+
+
+/// Rebuilds `value` iteratively: every value is passed to `pre` first, which either returns the final value via
+/// `ControlFlow::Break` or a value to descend into via `ControlFlow::Continue`; the children of the latter (sequence
+/// elements, map keys and values and associated enum values) are rebuilt before the value is passed to `post`
+pub(crate) fn rebuild<E, P, F>(value: AnyValue, mut pre: P, mut post: F) -> result::Result<AnyValue, E>
+    where P: FnMut(AnyValue) -> result::Result<ControlFlow<AnyValue, AnyValue>, E>,
+        F: FnMut(AnyValue) -> result::Result<AnyValue, E>
+{
+    let mut stack: Vec<RebuildFrame> = Vec::new();
+    let mut next = Some(value);
+    loop {
+        // Visit the next value or finish the innermost container if all of its children have been rebuilt
+        let finished = match next.take() {
+            Some(value) => match pre(value)? {
+                ControlFlow::Break(value) => Some(value),
+                ControlFlow::Continue(value) => match RebuildFrame::new(value) {
+                    Ok(frame) => {
+                        stack.push(frame);
+                        None
+                    },
+                    Err(value) => Some(post(value)?)
+                }
+            },
+            None => {
+                let frame = stack.pop().expect("There is no container to finish?!");
+                Some(post(frame.finish())?)
+            }
+        };
+
+        // Hand the finished value to its container and select the next child
+        if let Some(finished) = finished {
+            match stack.last_mut() {
+                Some(frame) => frame.rebuilt.push(finished),
+                None => return Ok(finished)
+            }
+        }
+        next = stack.last_mut().and_then(|frame| frame.children.next());
+    }
+}
+
+
+/// A pending step of `merge`
+enum MergeStep<'a> {
+    /// Merges the update into the target value at the location of the optional segment
+    Merge(&'a mut AnyValue, AnyValue, Option<Segment>),
+    /// Records the insertion of a new map entry at the location of the segment
+    Inserted(Segment),
+    /// Leaves the location of a merged value
+    Leave
+}
+
+
+/// A container that is being rebuilt
+struct RebuildFrame {
+    /// The variant of an enum or `None` if the container is not an enum
+    variant: Option<Utf8String>,
+    /// Whether the container is a map or not
+    is_map: bool,
+    /// The children that still need to be rebuilt
+    children: vec::IntoIter<AnyValue>,
+    /// The rebuilt children
+    rebuilt: Vec<AnyValue>
+}
+impl RebuildFrame {
+    /// Creates a new frame for `value` or returns `value` if it is not a container
+    pub fn new(value: AnyValue) -> result::Result<Self, AnyValue> {
+        let (variant, is_map, children) = match value {
+            AnyValue::Sequence(sequence) => (None, false, Vec::from(sequence)),
+            AnyValue::Map(map) => (None, true, map.into_iter().flat_map(|(key, value)| [key, value]).collect()),
+            AnyValue::Enum(enumeration) => {
+                let (variant, value) = enumeration.into_inner();
+                (Some(variant), false, vec![value])
+            },
+            value => return Err(value)
+        };
+        let rebuilt = Vec::with_capacity(children.len());
+        Ok(Self { variant, is_map, children: children.into_iter(), rebuilt })
+    }
+    /// Assembles the rebuilt container from the rebuilt children
+    pub fn finish(self) -> AnyValue {
+        let mut rebuilt = self.rebuilt.into_iter();
+        match self.variant {
+            Some(variant) => {
+                let value = rebuilt.next().unwrap_or(AnyValue::None);
+                AnyValue::Enum(Enumeration::with_value(variant.as_str(), value))
+            },
+            None if self.is_map => {
+                let mut map = Map::default();
+                while let (Some(key), Some(value)) = (rebuilt.next(), rebuilt.next()) {
+                    map.insert(key, value);
+                }
+                AnyValue::Map(map)
+            },
+            None => AnyValue::Sequence(Sequence::from_iter(rebuilt))
+        }
+    }
+}
+
+
+/// Whether `sequence` is non-empty and contains only integers within `0..=255`
+fn is_byte_sequence(sequence: &Sequence) -> bool {
+    let is_byte = |value: &AnyValue| match value {
+        AnyValue::Integer(integer) => u8::try_from(*integer).is_ok(),
+        _ => false
+    };
+    !sequence.is_empty() && sequence.iter().all(is_byte)
+}
+/// Converts the scalar map key `key` into a string
+fn stringify_key(key: AnyValue) -> Result<AnyValue> {
+    let string = match key {
+        AnyValue::Utf8String(key) => key,
+        AnyValue::Integer(Integer::UnsignedInteger(key)) => Utf8String::new(key),
+        AnyValue::Integer(Integer::SignedInteger(key)) => Utf8String::new(key),
+        AnyValue::Float(key) => Utf8String::new(*key),
+        AnyValue::Bool(key) => Utf8String::new(*key),
+        key => Err(etype!("Cannot represent a {} map key as string", key.kind()))?
+    };
+    Ok(AnyValue::Utf8String(string))
+}
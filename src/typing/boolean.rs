@@ -1,10 +1,10 @@
 //! Implements a type unifier for boolean like native types
 
 use crate::{
-    typing::AnyValue,
+    typing::{ AnyValue, Kind },
     error::{ Error, Result }
 };
-use std::{
+use core::{
     convert::TryFrom,
     ops::{ Deref, DerefMut }
 };
@@ -27,7 +27,7 @@ impl TryFrom<AnyValue> for Boolean {
     fn try_from(value: AnyValue) -> Result<Self> {
         match value {
             AnyValue::Bool(value) => Ok(value),
-            value => Err(etype!("Cannot convert {:?} to boolean", value))
+            value => Err(emismatch!(Kind::Bool, value))
         }
     }
 }
@@ -1,11 +1,12 @@
 //! Implements a type unifier for native byte types
 
 use crate::{
-    typing::AnyValue,
+    typing::{ AnyValue, Integer, Kind, Sequence },
     error::{ Error, Result }
 };
-use std::{
-    convert::TryFrom, iter::FromIterator,
+use alloc::vec::Vec;
+use core::{
+    convert::{ TryFrom, TryInto }, iter::FromIterator,
     ops::{ Deref, DerefMut }
 };
 
@@ -43,10 +44,16 @@ impl TryFrom<AnyValue> for Bytes {
     fn try_from(value: AnyValue) -> Result<Self> {
         match value {
             AnyValue::Bytes(value) => Ok(value),
-            value => Err(etype!("Cannot convert {:?} to bytes", value))
+            value => Err(emismatch!(Kind::Bytes, value))
         }
     }
 }
+impl TryFrom<Sequence> for Bytes {
+    type Error = Error;
+    fn try_from(value: Sequence) -> Result<Self> {
+        value.into_iter().map(|value| Integer::try_from(value)?.try_into()).collect()
+    }
+}
 impl Deref for Bytes {
     type Target = Vec<u8>;
     fn deref(&self) -> &Self::Target {
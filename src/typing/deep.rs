@@ -0,0 +1,350 @@
+//! Implements iterative algorithms for arbitrarily deep nested values
+//!
+//! The derived implementations of `Drop`, `Clone`, `PartialEq` and `Ord` recurse once per nesting level and overflow
+//! the stack on deeply nested values. The algorithms here keep their state on the heap instead.
+
+use crate::typing::{ AnyValue, Enumeration, Map };
+use alloc::{ collections::{ btree_map, BTreeMap }, string::String, vec::Vec };
+use core::{ cmp::Ordering, fmt::{ self, Debug, Formatter }, mem, slice };
+
+
+/// The nesting depth after which `debug` elides nested values
+const DEBUG_DEPTH: usize = 64;
+
+
+/// An iterator over the direct children of a container value
+enum Children<'a> {
+    /// The elements of a sequence
+    Sequence(slice::Iter<'a, AnyValue>),
+    /// The keys and values of a map in alternating order
+    Map(btree_map::Iter<'a, AnyValue, AnyValue>, Option<&'a AnyValue>),
+    /// The associated value of an enum
+    Enum(Option<&'a AnyValue>)
+}
+impl<'a> Children<'a> {
+    /// Creates an iterator over the direct children of `value` or `None` if `value` is not a container
+    pub fn of(value: &'a AnyValue) -> Option<Self> {
+        match value {
+            AnyValue::Sequence(sequence) => Some(Self::Sequence(sequence.iter())),
+            AnyValue::Map(map) => Some(Self::Map(map.iter(), None)),
+            AnyValue::Enum(enumeration) => Some(Self::Enum(Some(enumeration.value()))),
+            _ => None
+        }
+    }
+}
+impl<'a> Iterator for Children<'a> {
+    type Item = &'a AnyValue;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Sequence(elements) => elements.next(),
+            Self::Map(entries, pending_value) => match pending_value.take() {
+                Some(value) => Some(value),
+                None => {
+                    let (key, value) = entries.next()?;
+                    *pending_value = Some(value);
+                    Some(key)
+                }
+            },
+            Self::Enum(value) => value.take()
+        }
+    }
+}
+
+
+/// A container that is being cloned
+struct CloneFrame<'a> {
+    /// The variant of an enum or `None` if the container is not an enum
+    variant: Option<&'a String>,
+    /// Whether the container is a map or not
+    is_map: bool,
+    /// The children that still need to be cloned
+    children: Children<'a>,
+    /// The cloned children
+    cloned: Vec<AnyValue>
+}
+impl<'a> CloneFrame<'a> {
+    /// Creates a new frame for `value` or returns `None` if `value` is not a container
+    pub fn new(value: &'a AnyValue) -> Option<Self> {
+        let variant = match value {
+            AnyValue::Enum(enumeration) => Some(enumeration.variant()),
+            _ => None
+        };
+        let is_map = matches!(value, AnyValue::Map(_));
+        Some(Self { variant, is_map, children: Children::of(value)?, cloned: Vec::new() })
+    }
+    /// Assembles the cloned container from the cloned children
+    pub fn finish(self) -> AnyValue {
+        let mut cloned = self.cloned.into_iter();
+        match self.variant {
+            Some(variant) => {
+                let value = cloned.next().unwrap_or(AnyValue::None);
+                AnyValue::Enum(Enumeration::with_value(variant, value))
+            },
+            None if self.is_map => {
+                let mut map = BTreeMap::new();
+                while let (Some(key), Some(value)) = (cloned.next(), cloned.next()) {
+                    map.insert(key, value);
+                }
+                AnyValue::Map(Map::from(map))
+            },
+            None => AnyValue::Sequence(cloned.collect())
+        }
+    }
+}
+
+
+/// Clones `value` iteratively
+pub fn clone(value: &AnyValue) -> AnyValue {
+    let mut stack: Vec<CloneFrame> = Vec::new();
+    let mut next = value;
+    loop {
+        // Descend into containers or clone leaves directly
+        let mut finished = match CloneFrame::new(next) {
+            Some(frame) => {
+                stack.push(frame);
+                None
+            },
+            None => Some(clone_leaf(next))
+        };
+
+        // Ascend until we find a container with children that still need to be cloned
+        loop {
+            let frame = match stack.last_mut() {
+                Some(frame) => frame,
+                None => return finished.expect("The root value has not been cloned?!")
+            };
+            frame.cloned.extend(finished.take());
+            if let Some(child) = frame.children.next() {
+                next = child;
+                break;
+            }
+            finished = stack.pop().map(CloneFrame::finish);
+        }
+    }
+}
+
+
+/// Tests `left` and `right` for equality iteratively
+pub fn eq(left: &AnyValue, right: &AnyValue) -> bool {
+    compare(left, right, |left, right| match eq_leaf(left, right) {
+        true => Some(Ordering::Equal),
+        false => None
+    }) == Some(Ordering::Equal)
+}
+/// Compares `left` and `right` iteratively
+pub fn cmp(left: &AnyValue, right: &AnyValue) -> Ordering {
+    // Note: `cmp_leaf` never returns `None`
+    compare(left, right, |left, right| Some(cmp_leaf(left, right))).unwrap_or(Ordering::Equal)
+}
+
+
+/// Drops `values` iteratively
+pub fn drop(mut values: Vec<AnyValue>) {
+    while let Some(mut value) = values.pop() {
+        // Detach the children so that `value` can be dropped without recursion
+        match &mut value {
+            AnyValue::Sequence(sequence) => values.append(sequence),
+            AnyValue::Map(map) => for (key, value) in mem::take::<BTreeMap<_, _>>(map) {
+                values.push(key);
+                values.push(value);
+            },
+            AnyValue::Enum(enumeration) => values.push(mem::replace(enumeration.value_mut(), AnyValue::None)),
+            _ => ()
+        }
+    }
+}
+/// Formats `value` like a derived `Debug` implementation would, but elides values nested deeper than `DEBUG_DEPTH`
+pub fn debug(value: &AnyValue, f: &mut Formatter) -> fmt::Result {
+    Debugged { value, depth: 0 }.fmt(f)
+}
+
+
+/// Whether `value` is a container value or not
+pub fn is_container(value: &AnyValue) -> bool {
+    matches!(value, AnyValue::Sequence(_) | AnyValue::Map(_) | AnyValue::Enum(_))
+}
+
+
+/// Compares `left` and `right` lexicographically in the same order as a derived implementation would, where all
+/// values that are not both sequences, maps or enums are compared using `leaf`
+fn compare<F>(left: &AnyValue, right: &AnyValue, leaf: F) -> Option<Ordering>
+    where F: Fn(&AnyValue, &AnyValue) -> Option<Ordering>
+{
+    let mut stack: Vec<(Children, Children)> = Vec::new();
+    let (mut left, mut right) = (left, right);
+    loop {
+        // Descend into containers or compare leaves directly
+        match (left, right) {
+            (AnyValue::Enum(left_enum), AnyValue::Enum(right_enum)) => {
+                match left_enum.variant().cmp(right_enum.variant()) {
+                    Ordering::Equal => stack.push((Children::of(left)?, Children::of(right)?)),
+                    ordering => return Some(ordering)
+                }
+            },
+            (AnyValue::Sequence(_), AnyValue::Sequence(_)) | (AnyValue::Map(_), AnyValue::Map(_)) => {
+                stack.push((Children::of(left)?, Children::of(right)?))
+            },
+            (left, right) => match leaf(left, right) {
+                Some(Ordering::Equal) => (),
+                ordering => return ordering
+            }
+        }
+
+        // Ascend until we find a pair of containers with children that still need to be compared
+        loop {
+            let (left_children, right_children) = match stack.last_mut() {
+                Some(children) => children,
+                None => return Some(Ordering::Equal)
+            };
+            match (left_children.next(), right_children.next()) {
+                (Some(left_child), Some(right_child)) => {
+                    left = left_child;
+                    right = right_child;
+                    break;
+                },
+                (None, None) => {
+                    stack.pop();
+                },
+                (None, Some(_)) => return Some(Ordering::Less),
+                (Some(_), None) => return Some(Ordering::Greater)
+            }
+        }
+    }
+}
+
+
+/// Clones a value which is not a container
+fn clone_leaf(value: &AnyValue) -> AnyValue {
+    match value {
+        AnyValue::Bool(value) => AnyValue::Bool(*value),
+        AnyValue::Integer(value) => AnyValue::Integer(*value),
+        AnyValue::Float(value) => AnyValue::Float(*value),
+        AnyValue::Utf8String(value) => AnyValue::Utf8String(value.clone()),
+        AnyValue::Bytes(value) => AnyValue::Bytes(value.clone()),
+        AnyValue::Sequence(_) | AnyValue::Map(_) | AnyValue::Enum(_) => clone(value),
+        AnyValue::None => AnyValue::None
+    }
+}
+/// Tests two values for equality where at least one of them is not a container
+fn eq_leaf(left: &AnyValue, right: &AnyValue) -> bool {
+    match (left, right) {
+        (AnyValue::Bool(left), AnyValue::Bool(right)) => left == right,
+        (AnyValue::Integer(left), AnyValue::Integer(right)) => left == right,
+        (AnyValue::Float(left), AnyValue::Float(right)) => left == right,
+        (AnyValue::Utf8String(left), AnyValue::Utf8String(right)) => left == right,
+        (AnyValue::Bytes(left), AnyValue::Bytes(right)) => left == right,
+        (AnyValue::None, AnyValue::None) => true,
+        _ => false
+    }
+}
+/// Compares two values where at least one of them is not a container
+fn cmp_leaf(left: &AnyValue, right: &AnyValue) -> Ordering {
+    match (left, right) {
+        (AnyValue::Bool(left), AnyValue::Bool(right)) => left.cmp(right),
+        (AnyValue::Integer(left), AnyValue::Integer(right)) => left.cmp(right),
+        (AnyValue::Float(left), AnyValue::Float(right)) => left.cmp(right),
+        (AnyValue::Utf8String(left), AnyValue::Utf8String(right)) => left.cmp(right),
+        (AnyValue::Bytes(left), AnyValue::Bytes(right)) => left.cmp(right),
+        (left, right) => rank(left).cmp(&rank(right))
+    }
+}
+/// The position of the variant of `value` in the declaration order of `AnyValue`
+fn rank(value: &AnyValue) -> u8 {
+    match value {
+        AnyValue::Bool(_) => 0,
+        AnyValue::Integer(_) => 1,
+        AnyValue::Float(_) => 2,
+        AnyValue::Utf8String(_) => 3,
+        AnyValue::Bytes(_) => 4,
+        AnyValue::Sequence(_) => 5,
+        AnyValue::Map(_) => 6,
+        AnyValue::Enum(_) => 7,
+        AnyValue::None => 8
+    }
+}
+
+
+/// A value that is formatted at a given nesting depth
+struct Debugged<'a> {
+    /// The value to format
+    value: &'a AnyValue,
+    /// The nesting depth of the value
+    depth: usize
+}
+impl<'a> Debugged<'a> {
+    /// Wraps a direct child of the formatted value
+    fn child(&self, value: &'a AnyValue) -> Self {
+        Self { value, depth: self.depth + 1 }
+    }
+}
+impl Debug for Debugged<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        // Note: the wrapped containers are formatted like their derived implementations to keep the output unchanged
+        if self.depth >= DEBUG_DEPTH && is_container(self.value) {
+            return f.write_str("..");
+        }
+        match self.value {
+            AnyValue::Bool(value) => f.debug_tuple("Bool").field(value).finish(),
+            AnyValue::Integer(value) => f.debug_tuple("Integer").field(value).finish(),
+            AnyValue::Float(value) => f.debug_tuple("Float").field(value).finish(),
+            AnyValue::Utf8String(value) => f.debug_tuple("Utf8String").field(value).finish(),
+            AnyValue::Bytes(value) => f.debug_tuple("Bytes").field(value).finish(),
+            AnyValue::Sequence(_) => f.debug_tuple("Sequence").field(&Container(self)).finish(),
+            AnyValue::Map(_) => f.debug_tuple("Map").field(&Container(self)).finish(),
+            AnyValue::Enum(_) => f.debug_tuple("Enum").field(&Container(self)).finish(),
+            AnyValue::None => f.write_str("None")
+        }
+    }
+}
+
+
+/// The wrapped container of a formatted value
+struct Container<'a, 'b>(&'b Debugged<'a>);
+impl Debug for Container<'_, '_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let value = self.0;
+        match value.value {
+            AnyValue::Sequence(sequence) => {
+                let elements = Elements(sequence.iter().map(|element| value.child(element)).collect());
+                f.debug_struct("Sequence").field("inner", &elements).finish()
+            },
+            AnyValue::Map(map) => {
+                let entries = Entries(map.iter().map(|(key, entry)| (value.child(key), value.child(entry))).collect());
+                f.debug_struct("Map").field("inner", &entries).finish()
+            },
+            AnyValue::Enum(enumeration) => f.debug_struct("Enumeration")
+                .field("variant", &Variant(enumeration.variant()))
+                .field("value", &value.child(enumeration.value()))
+                .finish(),
+            _ => Debug::fmt(value, f)
+        }
+    }
+}
+
+
+/// The formatted variant of an enum
+struct Variant<'a>(&'a String);
+impl Debug for Variant<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Utf8String").field("inner", self.0).finish()
+    }
+}
+
+
+/// The formatted elements of a sequence
+struct Elements<'a>(Vec<Debugged<'a>>);
+impl Debug for Elements<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_list().entries(&self.0).finish()
+    }
+}
+
+
+/// The formatted entries of a map
+struct Entries<'a>(Vec<(Debugged<'a>, Debugged<'a>)>);
+impl Debug for Entries<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self.0.iter().map(|(key, value)| (key, value))).finish()
+    }
+}
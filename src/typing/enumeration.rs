@@ -2,9 +2,10 @@
 
 use crate::{
     error::{ Error, Result },
-    typing::{ AnyValue, utf8_string::Utf8String }
+    typing::{ AnyValue, Kind, deep, utf8_string::Utf8String }
 };
-use std::convert::TryFrom;
+use alloc::{ boxed::Box, string::{ String, ToString }, vec };
+use core::{ convert::TryFrom, mem };
 
 
 /// A type unifier for enumerations
@@ -43,8 +44,17 @@ impl Enumeration {
     }
 
     /// Returns the underlying variant and the associated tuple
-    pub fn into_inner(self) -> (Utf8String, AnyValue) {
-        (self.variant, *self.value)
+    pub fn into_inner(mut self) -> (Utf8String, AnyValue) {
+        let value = mem::replace(self.value_mut(), AnyValue::None);
+        (mem::take(&mut self.variant), value)
+    }
+}
+impl Drop for Enumeration {
+    fn drop(&mut self) {
+        // Note: an associated value without nested containers can be dropped as usual
+        if deep::is_container(&self.value) {
+            deep::drop(vec![mem::replace(self.value_mut(), AnyValue::None)]);
+        }
     }
 }
 impl From<String> for Enumeration {
@@ -62,7 +72,7 @@ impl TryFrom<AnyValue> for Enumeration {
     fn try_from(value: AnyValue) -> Result<Self> {
         match value {
             AnyValue::Enum(value) => Ok(value),
-            value => Err(etype!("Cannot convert {:?} to enum", value))
+            value => Err(emismatch!(Kind::Enum, value))
         }
     }
 }
@@ -1,22 +1,53 @@
 //! Implements a type unifier for float like native types
 
 use crate::{
-    typing::AnyValue,
+    typing::{ AnyValue, Kind },
     error::{ Error, Result }
 };
-use std::{
+use core::{
     cmp::Ordering, convert::TryFrom,
     ops::{ Deref, DerefMut }
 };
 
 
 /// A type unifier for floats
+#[allow(clippy::derive_ord_xor_partial_ord)] // The partial ordering is the numeric ordering of the wrapped float
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
 #[repr(transparent)]
 pub struct Float {
     /// The wrapped value
     inner: f64
 }
+impl Float {
+    /// Adds `other` to `self` or fails if the result is not finite
+    pub fn checked_add(self, other: Self) -> Result<Self> {
+        Self::finite(self.inner + other.inner).ok_or_else(|| einval!("Float overflow: {} + {}", self.inner, other.inner))
+    }
+    /// Subtracts `other` from `self` or fails if the result is not finite
+    pub fn checked_sub(self, other: Self) -> Result<Self> {
+        Self::finite(self.inner - other.inner).ok_or_else(|| einval!("Float overflow: {} - {}", self.inner, other.inner))
+    }
+    /// Multiplies `self` with `other` or fails if the result is not finite
+    pub fn checked_mul(self, other: Self) -> Result<Self> {
+        Self::finite(self.inner * other.inner).ok_or_else(|| einval!("Float overflow: {} * {}", self.inner, other.inner))
+    }
+    /// Divides `self` by `other` or fails if the result is not finite (e.g. because `other` is zero)
+    pub fn checked_div(self, other: Self) -> Result<Self> {
+        Self::finite(self.inner / other.inner).ok_or_else(|| einval!("Float overflow: {} / {}", self.inner, other.inner))
+    }
+    /// Negates `self` or fails if `self` is not finite
+    pub fn checked_neg(self) -> Result<Self> {
+        Self::finite(-self.inner).ok_or_else(|| einval!("Float overflow: -{}", self.inner))
+    }
+
+    /// Wraps `value` if it is finite
+    fn finite(value: f64) -> Option<Self> {
+        match value.is_finite() {
+            true => Some(Self::from(value)),
+            false => None
+        }
+    }
+}
 impl From<f64> for Float {
     fn from(value: f64) -> Self {
         Self { inner: value }
@@ -32,7 +63,7 @@ impl TryFrom<AnyValue> for Float {
     fn try_from(value: AnyValue) -> Result<Self> {
         match value {
             AnyValue::Float(value) => Ok(value),
-            value => Err(etype!("Cannot convert {:?} to float", value))
+            value => Err(emismatch!(Kind::Float, value))
         }
     }
 }
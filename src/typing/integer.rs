@@ -1,10 +1,10 @@
 //! Implements a type unifier for integer like native types
 
 use crate::{
-    typing::AnyValue,
+    typing::{ AnyValue, Kind },
     error::{ Error, Result }
 };
-use std::{ any, convert::TryFrom };
+use core::{ any, cmp::Ordering, convert::TryFrom };
 
 
 /// A type unifier for integers
@@ -15,6 +15,98 @@ pub enum Integer {
     /// A signed integer
     SignedInteger(i128)
 }
+impl Integer {
+    /// The magnitude of `i128::MIN` as `u128`
+    const I128_MIN_MAGNITUDE: u128 = 1 << 127;
+
+    /// Whether the integer is negative or not
+    pub fn is_negative(&self) -> bool {
+        matches!(self, Self::SignedInteger(value) if *value < 0)
+    }
+    /// Returns the integer in its canonical form (i.e. non-negative values are always `UnsignedInteger`s)
+    pub fn canonical(self) -> Self {
+        match self {
+            Self::SignedInteger(value) if value >= 0 => Self::UnsignedInteger(value as u128),
+            value => value
+        }
+    }
+    /// Compares two integers by their numeric value, regardless of the underlying representation
+    pub fn numeric_cmp(&self, other: &Self) -> Ordering {
+        match (self.to_parts(), other.to_parts()) {
+            ((false, left), (false, right)) => left.cmp(&right),
+            ((true, left), (true, right)) => right.cmp(&left),
+            ((false, _), (true, _)) => Ordering::Greater,
+            ((true, _), (false, _)) => Ordering::Less
+        }
+    }
+
+    /// Adds `other` to `self` or fails if the result cannot be represented
+    pub fn checked_add(self, other: Self) -> Result<Self> {
+        Self::add_parts(self.to_parts(), other.to_parts())
+            .ok_or_else(|| einval!("Integer overflow: {:?} + {:?}", self, other))
+    }
+    /// Subtracts `other` from `self` or fails if the result cannot be represented
+    pub fn checked_sub(self, other: Self) -> Result<Self> {
+        let (other_neg, other_mag) = other.to_parts();
+        Self::add_parts(self.to_parts(), (!other_neg, other_mag))
+            .ok_or_else(|| einval!("Integer overflow: {:?} - {:?}", self, other))
+    }
+    /// Multiplies `self` with `other` or fails if the result cannot be represented
+    pub fn checked_mul(self, other: Self) -> Result<Self> {
+        let ((self_neg, self_mag), (other_neg, other_mag)) = (self.to_parts(), other.to_parts());
+        self_mag.checked_mul(other_mag)
+            .and_then(|mag| Self::from_parts(self_neg != other_neg, mag))
+            .ok_or_else(|| einval!("Integer overflow: {:?} * {:?}", self, other))
+    }
+    /// Divides `self` by `other` (rounding towards zero) or fails if `other` is zero or the result cannot be represented
+    pub fn checked_div(self, other: Self) -> Result<Self> {
+        let ((self_neg, self_mag), (other_neg, other_mag)) = (self.to_parts(), other.to_parts());
+        if other_mag == 0 {
+            Err(einval!("Integer division by zero: {:?} / {:?}", self, other))?;
+        }
+        Self::from_parts(self_neg != other_neg, self_mag / other_mag)
+            .ok_or_else(|| einval!("Integer overflow: {:?} / {:?}", self, other))
+    }
+    /// Computes the remainder of `self / other` (with the sign of `self`) or fails if `other` is zero
+    pub fn checked_rem(self, other: Self) -> Result<Self> {
+        let ((self_neg, self_mag), (_, other_mag)) = (self.to_parts(), other.to_parts());
+        if other_mag == 0 {
+            Err(einval!("Integer division by zero: {:?} % {:?}", self, other))?;
+        }
+        Self::from_parts(self_neg, self_mag % other_mag)
+            .ok_or_else(|| einval!("Integer overflow: {:?} % {:?}", self, other))
+    }
+    /// Negates `self` or fails if the result cannot be represented
+    pub fn checked_neg(self) -> Result<Self> {
+        let (negative, magnitude) = self.to_parts();
+        Self::from_parts(!negative, magnitude).ok_or_else(|| einval!("Integer overflow: -{:?}", self))
+    }
+
+    /// Splits the integer into a sign (`true` if negative) and its magnitude
+    pub(crate) fn to_parts(self) -> (bool, u128) {
+        match self {
+            Self::UnsignedInteger(value) => (false, value),
+            Self::SignedInteger(value) => (value < 0, value.unsigned_abs())
+        }
+    }
+    /// Adds two integers given as sign-magnitude-pairs if the result is representable
+    fn add_parts((left_neg, left_mag): (bool, u128), (right_neg, right_mag): (bool, u128)) -> Option<Self> {
+        match left_neg == right_neg {
+            true => left_mag.checked_add(right_mag).and_then(|mag| Self::from_parts(left_neg, mag)),
+            false if left_mag >= right_mag => Self::from_parts(left_neg, left_mag - right_mag),
+            false => Self::from_parts(right_neg, right_mag - left_mag)
+        }
+    }
+    /// Creates a canonical integer from a sign (`true` if negative) and its magnitude if it is representable
+    pub(crate) fn from_parts(negative: bool, magnitude: u128) -> Option<Self> {
+        match (negative, magnitude) {
+            (false, magnitude) | (true, magnitude @ 0) => Some(Self::UnsignedInteger(magnitude)),
+            (true, Self::I128_MIN_MAGNITUDE) => Some(Self::SignedInteger(i128::MIN)),
+            (true, magnitude) if magnitude < Self::I128_MIN_MAGNITUDE => Some(Self::SignedInteger(-(magnitude as i128))),
+            _ => None
+        }
+    }
+}
 impl Default for Integer {
     fn default() -> Self {
         Self::UnsignedInteger(Default::default())
@@ -25,7 +117,7 @@ impl TryFrom<AnyValue> for Integer {
     fn try_from(value: AnyValue) -> Result<Self> {
         match value {
             AnyValue::Integer(value) => Ok(value),
-            value => Err(etype!("Cannot convert {:?} to integer", value))
+            value => Err(emismatch!(Kind::Integer, value))
         }
     }
 }
@@ -35,45 +127,52 @@ impl From<Integer> for AnyValue {
     }
 }
 macro_rules! impl_conversion {
-    ($type:ty) => {
+    (unsigned $type:ty) => {
         impl From<$type> for Integer {
             fn from(value: $type) -> Self {
-                if let Ok(value) = u128::try_from(value) {
-                    Self::UnsignedInteger(value)
-                } else if let Ok(value) = i128::try_from(value) {
-                    Self::SignedInteger(value)
-                } else {
-                    panic!("Cannot represent {} as `u128` nor as `i128`?!", value)
+                // Note: all unsigned native types are at most 128 bits wide, so this conversion is lossless
+                Self::UnsignedInteger(value as u128)
+            }
+        }
+        impl_conversion!(try_from $type);
+    };
+    (signed $type:ty) => {
+        impl From<$type> for Integer {
+            fn from(value: $type) -> Self {
+                // Note: all signed native types are at most 128 bits wide, so these conversions are lossless
+                match value {
+                    value if value < 0 => Self::SignedInteger(value as i128),
+                    value => Self::UnsignedInteger(value as u128)
                 }
             }
         }
+        impl_conversion!(try_from $type);
+    };
+    (try_from $type:ty) => {
         impl TryFrom<Integer> for $type {
             type Error = Error;
             fn try_from(value: Integer) -> Result<Self> {
-                let type_name = any::type_name::<$type>();
+                let out_of_range = || {
+                    let (min, max) = (Integer::from(<$type>::MIN), Integer::from(<$type>::MAX));
+                    Error::out_of_range(value, min, max, any::type_name::<$type>())
+                };
                 match value {
-                    Integer::UnsignedInteger(value) => {
-                        Self::try_from(value)
-                            .map_err(|e| etype!("Cannot represent {} as `{}` ({})", value, type_name, e))
-                    },
-                    Integer::SignedInteger(value) => {
-                        Self::try_from(value)
-                            .map_err(|e| etype!("Cannot represent {} as `{}` ({})", value, type_name, e))
-                    }
+                    Integer::UnsignedInteger(inner) => Self::try_from(inner).map_err(|_| out_of_range()),
+                    Integer::SignedInteger(inner) => Self::try_from(inner).map_err(|_| out_of_range())
                 }
             }
         }    
     };
 }
-impl_conversion!(u8);
-impl_conversion!(u16);
-impl_conversion!(u32);
-impl_conversion!(u64);
-impl_conversion!(u128);
-impl_conversion!(usize);
-impl_conversion!(i8);
-impl_conversion!(i16);
-impl_conversion!(i32);
-impl_conversion!(i64);
-impl_conversion!(i128);
-impl_conversion!(isize);
+impl_conversion!(unsigned u8);
+impl_conversion!(unsigned u16);
+impl_conversion!(unsigned u32);
+impl_conversion!(unsigned u64);
+impl_conversion!(unsigned u128);
+impl_conversion!(unsigned usize);
+impl_conversion!(signed i8);
+impl_conversion!(signed i16);
+impl_conversion!(signed i32);
+impl_conversion!(signed i64);
+impl_conversion!(signed i128);
+impl_conversion!(signed isize);
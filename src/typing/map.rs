@@ -1,11 +1,12 @@
 //! Implements a type unifier for map like native types
 
 use crate::{
-    typing::AnyValue,
+    typing::{ AnyValue, Kind, deep },
     error::{ Error, Result }
 };
-use std::{
-    collections::BTreeMap, convert::TryFrom, iter::FromIterator,
+use alloc::{ collections::BTreeMap, vec::Vec };
+use core::{
+    convert::TryFrom, iter::FromIterator, mem,
     ops::{ Deref, DerefMut }
 };
 
@@ -17,6 +18,15 @@ pub struct Map {
     /// The wrapped value
     inner: BTreeMap<AnyValue, AnyValue>
 }
+impl Drop for Map {
+    fn drop(&mut self) {
+        // Note: maps without nested containers can be dropped as usual
+        if self.inner.iter().any(|(key, value)| deep::is_container(key) || deep::is_container(value)) {
+            let entries = mem::take(&mut self.inner).into_iter();
+            deep::drop(entries.flat_map(|(key, value)| [key, value]).collect::<Vec<_>>());
+        }
+    }
+}
 impl From<BTreeMap<AnyValue, AnyValue>> for Map {
     fn from(value: BTreeMap<AnyValue, AnyValue>) -> Self {
         Self { inner: value }
@@ -27,7 +37,7 @@ impl TryFrom<AnyValue> for Map {
     fn try_from(value: AnyValue) -> Result<Self> {
         match value {
             AnyValue::Map(value) => Ok(value),
-            value => Err(etype!("Cannot convert {:?} to map", value))
+            value => Err(emismatch!(Kind::Map, value))
         }
     }
 }
@@ -53,15 +63,15 @@ impl AsMut<BTreeMap<AnyValue, AnyValue>> for Map {
     }
 }
 impl From<Map> for BTreeMap<AnyValue, AnyValue> {
-    fn from(value: Map) -> Self {
-        value.inner
+    fn from(mut value: Map) -> Self {
+        mem::take(&mut value.inner)
     }
 }
 impl IntoIterator for Map {
     type Item = (AnyValue, AnyValue);
     type IntoIter = <BTreeMap<AnyValue, AnyValue> as IntoIterator>::IntoIter;
     fn into_iter(self) -> Self::IntoIter {
-        self.inner.into_iter()
+        BTreeMap::from(self).into_iter()
     }
 }
 impl FromIterator<(AnyValue, AnyValue)> for Map {
@@ -7,19 +7,28 @@ pub mod float;
 pub mod sequence;
 pub mod map;
 pub mod integer;
+pub mod number;
 pub mod utf8_string;
+mod deep;
 
 use crate::error::{ Error, Result };
-use std::convert::TryFrom;
+use core::{
+    cmp::Ordering, convert::TryFrom,
+    fmt::{ self, Debug, Display, Formatter }
+};
 // Reexport types
 pub use crate::typing::{
     boolean::Boolean, bytes::Bytes, enumeration::Enumeration, float::Float,
-    sequence::Sequence, map::Map, integer::Integer, utf8_string::Utf8String
+    sequence::Sequence, map::Map, integer::Integer, number::Number, utf8_string::Utf8String
 };
 
 
 /// A simplified type model which can represent various data structures and allows dynamic runtime inspection
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+///
+/// # Note
+/// `Clone`, `PartialEq` and `Ord` are implemented iteratively, and nested sequences, maps and enums are dropped
+/// iteratively, so that even very deeply nested values cannot overflow the stack. `Debug` elides values nested deeper
+/// than 64 levels for the same reason.
 #[non_exhaustive]
 pub enum AnyValue {
     /// A boolean value
@@ -41,6 +50,50 @@ pub enum AnyValue {
     /// A non-existant value
     None
 }
+impl AnyValue {
+    /// The kind of the value
+    pub fn kind(&self) -> Kind {
+        match self {
+            Self::Bool(_) => Kind::Bool,
+            Self::Integer(_) => Kind::Integer,
+            Self::Float(_) => Kind::Float,
+            Self::Utf8String(_) => Kind::Utf8String,
+            Self::Bytes(_) => Kind::Bytes,
+            Self::Sequence(_) => Kind::Sequence,
+            Self::Map(_) => Kind::Map,
+            Self::Enum(_) => Kind::Enum,
+            Self::None => Kind::None
+        }
+    }
+}
+impl Debug for AnyValue {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        deep::debug(self, f)
+    }
+}
+impl Clone for AnyValue {
+    fn clone(&self) -> Self {
+        deep::clone(self)
+    }
+}
+impl PartialEq for AnyValue {
+    fn eq(&self, other: &Self) -> bool {
+        deep::eq(self, other)
+    }
+}
+impl Eq for AnyValue {
+    /* Nothing to see here */
+}
+impl PartialOrd for AnyValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for AnyValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        deep::cmp(self, other)
+    }
+}
 impl From<()> for AnyValue {
     fn from(_unit: ()) -> Self {
         Self::None
@@ -51,7 +104,47 @@ impl TryFrom<AnyValue> for () {
     fn try_from(value: AnyValue) -> Result<Self> {
         match value {
             AnyValue::None => Ok(()),
-            value => Err(etype!("Cannot convert {:?} to unit", value))
+            value => Err(emismatch!(Kind::None, value))
+        }
+    }
+}
+
+
+/// The kind of an `AnyValue`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Kind {
+    /// A boolean value
+    Bool,
+    /// An integer value
+    Integer,
+    /// A floating point value
+    Float,
+    /// An UTF-8 string value
+    Utf8String,
+    /// A binary vector
+    Bytes,
+    /// A sequence
+    Sequence,
+    /// A map
+    Map,
+    /// An enum value
+    Enum,
+    /// A non-existant value
+    None
+}
+impl Display for Kind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Bool => write!(f, "boolean"),
+            Self::Integer => write!(f, "integer"),
+            Self::Float => write!(f, "float"),
+            Self::Utf8String => write!(f, "UTF-8 string"),
+            Self::Bytes => write!(f, "bytes"),
+            Self::Sequence => write!(f, "sequence"),
+            Self::Map => write!(f, "map"),
+            Self::Enum => write!(f, "enum"),
+            Self::None => write!(f, "unit")
         }
     }
 }
@@ -0,0 +1,238 @@
+//! Implements a numeric abstraction over integers and floats
+//!
+//! ## Comparison policy
+//! Numbers are always compared by their exact mathematical value, i.e. an integer is never rounded to `f64` before it is
+//! compared against a float. This matters for integers above `2^53` (or below `-2^53`) which cannot be represented
+//! exactly as `f64`: `2^53 + 1` compares greater than the float `2^53`, even though `(2^53 + 1) as f64 == 2^53`.
+//! Integers compare by value regardless of their representation, so `SignedInteger(7)` equals `UnsignedInteger(7)`.
+//!
+//! `NaN` is unordered against every number including itself, so `partial_cmp` returns `None` and `==` returns `false`.
+//! If you need a total order, use `Number::total_cmp`, which sorts negative `NaN`s before and positive `NaN`s after all
+//! other numbers.
+//!
+//! ## Arithmetic policy
+//! Arithmetic on two integers is exact and fails on overflow. As soon as a float is involved, the integer operand is
+//! converted to `f64` (which rounds integers above `2^53` to the nearest representable float) and the result must be
+//! finite.
+
+use crate::{
+    typing::{ AnyValue, Float, Integer, Kind },
+    error::{ Error, Result }
+};
+use core::{ cmp::Ordering, convert::TryFrom };
+
+
+/// `2^128` as `f64`, the first float above the range of `Integer`
+const INTEGER_UPPER_BOUND: f64 = 340282366920938463463374607431768211456.0;
+/// `-2^127` as `f64`, the lowest float within the range of `Integer`
+const INTEGER_LOWER_BOUND: f64 = -170141183460469231731687303715884105728.0;
+/// `2^52` as `f64`, the magnitude from which on floats cannot have a fractional part
+const INTEGRAL_BOUND: f64 = 4503599627370496.0;
+
+
+/// A numeric value which is either an integer or a float
+#[derive(Debug, Clone, Copy)]
+pub enum Number {
+    /// An integer number
+    Integer(Integer),
+    /// A floating point number
+    Float(Float)
+}
+impl Number {
+    /// Converts the number into a `f64`
+    ///
+    /// This conversion is lossy for integers above `2^53` or below `-2^53`, which are rounded to the nearest `f64`.
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            Self::Integer(Integer::UnsignedInteger(value)) => *value as f64,
+            Self::Integer(Integer::SignedInteger(value)) => *value as f64,
+            Self::Float(value) => **value
+        }
+    }
+    /// Converts the number into an integer if this is possible without loss of precision
+    ///
+    /// A float can only be converted if it has no fractional part and is within the range of `Integer`.
+    pub fn to_integer(&self) -> Result<Integer> {
+        match self {
+            Self::Integer(value) => Ok(*value),
+            Self::Float(value) => float_to_integer(**value)
+                .ok_or_else(|| etype!("Cannot represent {} as integer without loss of precision", **value))
+        }
+    }
+
+    /// Adds `other` to `self` (see the module-level arithmetic policy)
+    pub fn checked_add(self, other: Self) -> Result<Self> {
+        match (self, other) {
+            (Self::Integer(left), Self::Integer(right)) => left.checked_add(right).map(Self::Integer),
+            (left, right) => Float::from(left.as_f64()).checked_add(right.as_f64().into()).map(Self::Float)
+        }
+    }
+    /// Subtracts `other` from `self` (see the module-level arithmetic policy)
+    pub fn checked_sub(self, other: Self) -> Result<Self> {
+        match (self, other) {
+            (Self::Integer(left), Self::Integer(right)) => left.checked_sub(right).map(Self::Integer),
+            (left, right) => Float::from(left.as_f64()).checked_sub(right.as_f64().into()).map(Self::Float)
+        }
+    }
+    /// Multiplies `self` with `other` (see the module-level arithmetic policy)
+    pub fn checked_mul(self, other: Self) -> Result<Self> {
+        match (self, other) {
+            (Self::Integer(left), Self::Integer(right)) => left.checked_mul(right).map(Self::Integer),
+            (left, right) => Float::from(left.as_f64()).checked_mul(right.as_f64().into()).map(Self::Float)
+        }
+    }
+    /// Divides `self` by `other` (see the module-level arithmetic policy)
+    ///
+    /// Note that the division of two integers is an integer division which rounds towards zero.
+    pub fn checked_div(self, other: Self) -> Result<Self> {
+        match (self, other) {
+            (Self::Integer(left), Self::Integer(right)) => left.checked_div(right).map(Self::Integer),
+            (left, right) => Float::from(left.as_f64()).checked_div(right.as_f64().into()).map(Self::Float)
+        }
+    }
+    /// Negates `self`
+    pub fn checked_neg(self) -> Result<Self> {
+        match self {
+            Self::Integer(value) => value.checked_neg().map(Self::Integer),
+            Self::Float(value) => value.checked_neg().map(Self::Float)
+        }
+    }
+
+    /// Compares two numbers using a total order
+    ///
+    /// This is the same order as `partial_cmp` except that negative `NaN`s are sorted before and positive `NaN`s after all
+    /// other numbers; two `NaN`s are ordered by their bit pattern.
+    pub fn total_cmp(&self, other: &Self) -> Ordering {
+        if let Some(ordering) = self.partial_cmp(other) {
+            return ordering;
+        }
+
+        // At least one side is `NaN`, so we sort it depending on its sign
+        let nan_rank = |number: &Self| match number {
+            Self::Float(value) if value.is_nan() && value.is_sign_negative() => -1,
+            Self::Float(value) if value.is_nan() => 1,
+            _ => 0
+        };
+        match (self, other) {
+            (Self::Float(left), Self::Float(right)) if left.is_nan() && right.is_nan() => left.cmp(right),
+            (left, right) => nan_rank(left).cmp(&nan_rank(right))
+        }
+    }
+}
+impl From<Integer> for Number {
+    fn from(value: Integer) -> Self {
+        Self::Integer(value)
+    }
+}
+impl From<Float> for Number {
+    fn from(value: Float) -> Self {
+        Self::Float(value)
+    }
+}
+impl TryFrom<AnyValue> for Number {
+    type Error = Error;
+    fn try_from(value: AnyValue) -> Result<Self> {
+        match value {
+            AnyValue::Integer(value) => Ok(Self::Integer(value)),
+            AnyValue::Float(value) => Ok(Self::Float(value)),
+            // Note: there is no dedicated number kind, so floats are reported as the expected superset
+            value => Err(emismatch!(Kind::Float, value))
+        }
+    }
+}
+impl TryFrom<Number> for Integer {
+    type Error = Error;
+    fn try_from(value: Number) -> Result<Self> {
+        value.to_integer()
+    }
+}
+impl From<Number> for Float {
+    fn from(value: Number) -> Self {
+        Float::from(value.as_f64())
+    }
+}
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Integer(left), Self::Integer(right)) => Some(left.numeric_cmp(right)),
+            (Self::Integer(left), Self::Float(right)) => cmp_integer_float(*left, **right),
+            (Self::Float(left), Self::Integer(right)) => cmp_integer_float(*right, **left).map(Ordering::reverse),
+            (Self::Float(left), Self::Float(right)) => (**left).partial_cmp(&**right)
+        }
+    }
+}
+impl From<Number> for AnyValue {
+    fn from(value: Number) -> Self {
+        match value {
+            Number::Integer(value) => AnyValue::Integer(value),
+            Number::Float(value) => AnyValue::Float(value)
+        }
+    }
+}
+
+
+impl PartialEq<Float> for Integer {
+    fn eq(&self, other: &Float) -> bool {
+        Number::Integer(*self) == Number::Float(*other)
+    }
+}
+impl PartialOrd<Float> for Integer {
+    fn partial_cmp(&self, other: &Float) -> Option<Ordering> {
+        cmp_integer_float(*self, **other)
+    }
+}
+impl PartialEq<Integer> for Float {
+    fn eq(&self, other: &Integer) -> bool {
+        Number::Float(*self) == Number::Integer(*other)
+    }
+}
+impl PartialOrd<Integer> for Float {
+    fn partial_cmp(&self, other: &Integer) -> Option<Ordering> {
+        cmp_integer_float(*other, **self).map(Ordering::reverse)
+    }
+}
+
+
+/// Converts `value` into an integer if it is integral and within the range of `Integer`
+fn float_to_integer(value: f64) -> Option<Integer> {
+    match value {
+        value if !value.is_finite() || trunc(value) != value => None,
+        value if !(INTEGER_LOWER_BOUND..INTEGER_UPPER_BOUND).contains(&value) => None,
+        value if value < 0.0 => Some(Integer::SignedInteger(value as i128)),
+        value => Some(Integer::UnsignedInteger(value as u128))
+    }
+}
+
+/// Truncates the fractional part of `value` (`f64::trunc` is not available without `std`)
+fn trunc(value: f64) -> f64 {
+    // Note: NaN and infinities are returned as-is
+    match value {
+        value if value > -INTEGRAL_BOUND && value < INTEGRAL_BOUND => value as i64 as f64,
+        value => value
+    }
+}
+
+
+/// Compares an integer against a float by their exact mathematical value
+fn cmp_integer_float(integer: Integer, float: f64) -> Option<Ordering> {
+    match float {
+        float if float.is_nan() => None,
+        float if float >= INTEGER_UPPER_BOUND => Some(Ordering::Less),
+        float if float < INTEGER_LOWER_BOUND => Some(Ordering::Greater),
+        float => {
+            // Compare the integral part exactly, and use the fractional part as tie-breaker
+            let truncated = float_to_integer(trunc(float))?;
+            let ordering = integer.numeric_cmp(&truncated);
+            match (ordering, float - trunc(float)) {
+                (Ordering::Equal, fraction) if fraction > 0.0 => Some(Ordering::Less),
+                (Ordering::Equal, fraction) if fraction < 0.0 => Some(Ordering::Greater),
+                (ordering, _) => Some(ordering)
+            }
+        }
+    }
+}
@@ -1,11 +1,12 @@
 //! Implements a type unifier for sequence like native types
 
 use crate::{
-    typing::AnyValue,
+    typing::{ AnyValue, Bytes, Integer, Kind, deep },
     error::{ Error, Result }
 };
-use std::{
-    convert::TryFrom, iter::FromIterator,
+use alloc::vec::Vec;
+use core::{
+    convert::TryFrom, iter::FromIterator, mem,
     ops::{ Deref, DerefMut }
 };
 
@@ -23,6 +24,11 @@ impl Sequence {
         Self { inner: value.into() }
     }
 }
+impl Drop for Sequence {
+    fn drop(&mut self) {
+        deep::drop(mem::take(&mut self.inner))
+    }
+}
 impl From<Vec<AnyValue>> for Sequence {
     fn from(value: Vec<AnyValue>) -> Self {
         Self::new(value)
@@ -38,12 +44,17 @@ impl<const N: usize> From<[AnyValue; N]> for Sequence {
         Self::new(value)
     }
 }
+impl From<Bytes> for Sequence {
+    fn from(value: Bytes) -> Self {
+        value.into_iter().map(|byte| AnyValue::Integer(Integer::from(byte))).collect()
+    }
+}
 impl TryFrom<AnyValue> for Sequence {
     type Error = Error;
     fn try_from(value: AnyValue) -> Result<Self> {
         match value {
             AnyValue::Sequence(value) => Ok(value),
-            value => Err(etype!("Cannot convert {:?} to sequence", value))
+            value => Err(emismatch!(Kind::Sequence, value))
         }
     }
 }
@@ -79,15 +90,15 @@ impl AsMut<[AnyValue]> for Sequence {
     }
 }
 impl From<Sequence> for Vec<AnyValue> {
-    fn from(value: Sequence) -> Self {
-        value.inner
+    fn from(mut value: Sequence) -> Self {
+        mem::take(&mut value.inner)
     }
 }
 impl<const N: usize> TryFrom<Sequence> for [AnyValue; N] {
     type Error = Error;
     fn try_from(value: Sequence) -> Result<Self> {
         let len = value.len();
-        Self::try_from(value.inner)
+        Self::try_from(Vec::from(value))
             .map_err(|_| etype!("Cannot represent {} elements as `[AnyType; {}]`", len, N))
     }
 }
@@ -95,7 +106,7 @@ impl IntoIterator for Sequence {
     type Item = AnyValue;
     type IntoIter = <Vec<AnyValue> as IntoIterator>::IntoIter;
     fn into_iter(self) -> Self::IntoIter {
-        self.inner.into_iter()
+        Vec::from(self).into_iter()
     }
 }
 impl FromIterator<AnyValue> for Sequence {
@@ -1,10 +1,11 @@
 //! Implements a type unifier for UTF-8 string like native types
 
 use crate::{
-    typing::AnyValue,
+    typing::{ AnyValue, Kind },
     error::{ Error, Result }
 };
-use std::{
+use alloc::string::{ String, ToString };
+use core::{
     convert::TryFrom,
     fmt::{ self, Display, Formatter },
     ops::{ Deref, DerefMut }
@@ -44,7 +45,7 @@ impl TryFrom<AnyValue> for Utf8String {
     fn try_from(value: AnyValue) -> Result<Self> {
         match value {
             AnyValue::Utf8String(value) => Ok(value),
-            value => Err(etype!("Cannot convert {:?} to UTF-8 string", value))
+            value => Err(emismatch!(Kind::Utf8String, value))
         }
     }
 }
@@ -61,7 +62,7 @@ impl DerefMut for Utf8String {
 }
 impl Display for Utf8String {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{}", self)
+        write!(f, "{}", self.inner)
     }
 }
 impl AsRef<String> for Utf8String {
@@ -98,7 +99,7 @@ impl TryFrom<Utf8String> for char {
             .ok_or(etype!("Cannot convert an empty string to `char`"))?;
 
         // Ensure that the string is empty
-        if !chars.next().is_none() {
+        if chars.next().is_some() {
             Err(etype!("Cannot convert a multichar string to a single `char`"))?;
         }
         Ok(first)
@@ -0,0 +1,121 @@
+#[macro_use] extern crate serde_derive;
+mod common;
+
+use common::{ map, roundtrip };
+use serde_dynamic_typing::{ from_typed, typing::{ AnyValue, Enumeration, Integer } };
+use std::{ collections::BTreeMap, iter::FromIterator };
+
+
+/// A struct with flattened fields
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Flattened {
+    id: u64,
+    #[serde(flatten)]
+    inner: Inner,
+    #[serde(flatten)]
+    extra: BTreeMap<String, i32>
+}
+/// The flattened struct
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Inner {
+    small: u8,
+    signed: i16,
+    large: u64,
+    shape: Shape
+}
+
+
+/// An externally tagged enum
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Shape {
+    Empty,
+    Circle(u8),
+    Line(u8, u8),
+    Rect { width: u8, height: u8 }
+}
+
+
+/// An untagged enum
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+enum Untagged {
+    Integer(i8),
+    Shape(Shape),
+    Text(String),
+    Pair(u16, u16),
+    Named { name: String }
+}
+
+
+/// An internally tagged enum
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum Internal {
+    Empty,
+    Point { x: i64, y: u64 },
+    Wrapped(Inner),
+    Shape { shape: Shape }
+}
+
+
+/// An adjacently tagged enum
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "t", content = "c")]
+enum Adjacent {
+    Empty,
+    Value(Shape)
+}
+
+
+/// Creates a sample inner struct
+fn inner() -> Inner {
+    Inner { small: 7, signed: -7, large: u64::MAX, shape: Shape::Rect { width: 3, height: 4 } }
+}
+
+
+#[test]
+fn flatten() {
+    let extra = BTreeMap::from_iter(vec![(String::from("a"), -1), (String::from("b"), 2)]);
+    roundtrip(&Flattened { id: u64::MAX, inner: inner(), extra });
+
+    // Native enums are accepted within flattened structs
+    let value = map(vec![
+        ("id", Integer::from(1u8).into()),
+        ("small", Integer::from(2u8).into()),
+        ("signed", Integer::from(-3i8).into()),
+        ("large", Integer::from(4u8).into()),
+        ("shape", AnyValue::Enum(Enumeration::with_value("Circle", Integer::from(5u8))))
+    ]);
+    let flattened: Flattened = from_typed(value).expect("Failed to deserialize flattened struct");
+    assert_eq!(flattened.inner, Inner { small: 2, signed: -3, large: 4, shape: Shape::Circle(5) });
+}
+
+
+#[test]
+fn untagged() {
+    roundtrip(&Untagged::Integer(-7));
+    roundtrip(&Untagged::Text(String::from("text")));
+    roundtrip(&Untagged::Pair(1, 2));
+    roundtrip(&Untagged::Shape(Shape::Empty));
+    roundtrip(&Untagged::Shape(Shape::Circle(7)));
+    roundtrip(&Untagged::Shape(Shape::Line(1, 2)));
+    roundtrip(&Untagged::Shape(Shape::Rect { width: 3, height: 4 }));
+    roundtrip(&Untagged::Named { name: String::from("name") });
+}
+
+
+#[test]
+fn internally_tagged() {
+    roundtrip(&Internal::Empty);
+    roundtrip(&Internal::Point { x: i64::MIN, y: u64::MAX });
+    roundtrip(&Internal::Wrapped(inner()));
+    roundtrip(&Internal::Shape { shape: Shape::Line(1, 2) });
+}
+
+
+#[test]
+fn adjacently_tagged() {
+    roundtrip(&Adjacent::Empty);
+    roundtrip(&Adjacent::Value(Shape::Empty));
+    roundtrip(&Adjacent::Value(Shape::Rect { width: 3, height: 4 }));
+}
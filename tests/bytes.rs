@@ -1,5 +1,13 @@
 #[macro_use] extern crate serde_derive;
-use serde_dynamic_typing::typing::AnyValue;
+mod common;
+
+use common::integers;
+use serde_bytes::ByteBuf;
+use serde_dynamic_typing::{
+    de::{ self, Coercion }, ser, tree,
+    typing::{ AnyValue, Bytes, Sequence }
+};
+use std::iter::FromIterator;
 
 
 #[test]
@@ -19,3 +27,122 @@ fn bytes() {
     let deserialized: ByteContainer = serde_dynamic_typing::from_typed(serialized).expect("Failed to deserialize bytes");
     assert_eq!(deserialized, value);
 }
+
+
+#[test]
+fn sequences_as_bytes() {
+    // Sequences of integers can be read as bytes if allowed...
+    de::from_typed::<ByteBuf>(integers(&[1u8, 2, 3])).expect_err("Deserialized sequence as bytes without coercion?!");
+    let options = de::Options::new().coerce(Coercion::SequenceToBytes);
+    let (bytes, report) = de::from_typed_with::<ByteBuf>(integers(&[1u8, 2, 3]), &options)
+        .expect("Failed to deserialize sequence as bytes");
+    assert_eq!(bytes.as_slice(), &[1, 2, 3]);
+    assert_eq!(report.coercions(), &[Coercion::SequenceToBytes]);
+    de::from_typed_with::<ByteBuf>(integers(&[1u16, 256]), &options).expect_err("Deserialized 256 as byte?!");
+
+    // ...and bytes can be read as sequences or tuples
+    let vec: Vec<u8> = de::from_typed(Bytes::from(vec![1, 2, 3]).into()).expect("Failed to deserialize bytes as vec");
+    assert_eq!(vec, vec![1, 2, 3]);
+    let array: [u16; 2] = de::from_typed(Bytes::from(vec![1, 2]).into()).expect("Failed to deserialize bytes as array");
+    assert_eq!(array, [1, 2]);
+}
+
+
+#[test]
+fn collapse_bytes() {
+    let options = ser::Options::new().collapse_bytes(true);
+
+    // `u8`-sequences are only collapsed if enabled
+    assert_eq!(ser::to_typed(&vec![1u8, 2]).expect("Failed to serialize vec"), integers(&[1u8, 2]));
+    let serialized = ser::to_typed_with(&vec![1u8, 2], &options).expect("Failed to serialize vec");
+    assert_eq!(serialized, AnyValue::Bytes(Bytes::from(vec![1, 2])));
+    let serialized = ser::to_typed_with(&[[7u8; 2]; 2], &options).expect("Failed to serialize array");
+    assert_eq!(serialized, AnyValue::Sequence(Sequence::from_iter(vec![
+        AnyValue::Bytes(Bytes::from(vec![7, 7])), AnyValue::Bytes(Bytes::from(vec![7, 7]))
+    ])));
+
+    // Sequences of other types and empty sequences are kept
+    assert_eq!(ser::to_typed_with(&vec![1u16, 2], &options).expect("Failed to serialize vec"), integers(&[1u16, 2]));
+    assert_eq!(ser::to_typed_with(&Vec::<u8>::new(), &options).expect("Failed to serialize vec"), integers::<u8>(&[]));
+    let serialized = ser::to_typed_with(&vec![(1u8, 2u16)], &options).expect("Failed to serialize vec");
+    assert_eq!(serialized, AnyValue::Sequence(Sequence::from_iter(vec![integers(&[1u8, 2])])));
+}
+
+
+#[test]
+fn normalize() {
+    let value = AnyValue::Sequence(Sequence::from_iter(vec![
+        integers(&[1u16, 255]), integers(&[1u16, 256]), integers::<u8>(&[])
+    ]));
+    let normalized = tree::collapse_bytes(value);
+    assert_eq!(normalized, AnyValue::Sequence(Sequence::from_iter(vec![
+        AnyValue::Bytes(Bytes::from(vec![1, 255])), integers(&[1u16, 256]), integers::<u8>(&[])
+    ])));
+}
+
+
+#[test]
+fn collapsed_flatten() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Inner {
+        #[serde(with = "serde_bytes")]
+        data: Vec<u8>
+    }
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Outer {
+        name: String,
+        #[serde(flatten)]
+        inner: Inner
+    }
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct PlainInner {
+        data: Vec<u8>
+    }
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Plain {
+        name: String,
+        #[serde(flatten)]
+        inner: PlainInner
+    }
+
+    // Flattened byte containers can be read from collapsed bytes...
+    let options = ser::Options::new().collapse_bytes(true);
+    let value = Outer { name: String::from("test"), inner: Inner { data: vec![1, 2, 3] } };
+    let serialized = ser::to_typed_with(&value, &options).expect("Failed to serialize flattened struct");
+    let deserialized: Outer = de::from_typed(serialized).expect("Failed to deserialize flattened bytes");
+    assert_eq!(deserialized, value);
+
+    // ...but flattened plain vectors cannot since serde buffers them as byte buffers
+    let value = Plain { name: String::from("test"), inner: PlainInner { data: vec![1, 2, 3] } };
+    let serialized = ser::to_typed_with(&value, &options).expect("Failed to serialize flattened struct");
+    de::from_typed::<Plain>(serialized).expect_err("Deserialized flattened plain vector from bytes?!");
+    let serialized = ser::to_typed(&value).expect("Failed to serialize flattened struct");
+    let deserialized: Plain = de::from_typed(serialized).expect("Failed to deserialize flattened sequence");
+    assert_eq!(deserialized, value);
+}
+
+
+#[test]
+fn collapsed_untagged() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(untagged)]
+    enum Untagged {
+        Text(String),
+        Integers(Vec<u32>)
+    }
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(untagged)]
+    enum Binary {
+        Bytes(ByteBuf)
+    }
+
+    // Byte buffers can be read from bytes...
+    let deserialized: Binary = de::from_typed(Bytes::from(vec![1, 2]).into()).expect("Failed to deserialize bytes");
+    assert_eq!(deserialized, Binary::Bytes(ByteBuf::from(vec![1, 2])));
+
+    // ...but widened integer sequences cannot be read back from collapsed bytes
+    let value = Untagged::Integers(vec![1, 255]);
+    let serialized = tree::collapse_bytes(ser::to_typed(&value).expect("Failed to serialize untagged enum"));
+    assert_eq!(serialized, AnyValue::Bytes(Bytes::from(vec![1, 255])));
+    de::from_typed::<Untagged>(serialized).expect_err("Deserialized untagged integers from bytes?!");
+}
@@ -0,0 +1,108 @@
+#[macro_use] extern crate serde_derive;
+mod common;
+
+use common::{ map, string };
+use serde_dynamic_typing::{
+    de::{ Coercion, Options },
+    typing::{ AnyValue, Float, Integer, Sequence, Utf8String }
+};
+use std::iter::FromIterator;
+
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Config {
+    port: u32,
+    ratio: f64,
+    enabled: bool,
+    name: String,
+    threshold: i64,
+    hosts: Vec<String>
+}
+
+
+#[test]
+fn lenient() {
+    let value = map(vec![
+        ("port", string(" 42")),
+        ("ratio", Integer::from(1u8).into()),
+        ("enabled", Integer::from(1u8).into()),
+        ("name", Integer::from(-7i8).into()),
+        ("threshold", Float::from(-3.0).into()),
+        ("hosts", string("localhost"))
+    ]);
+    let expected = Config {
+        port: 42, ratio: 1.0, enabled: true, name: "-7".to_string(), threshold: -3,
+        hosts: vec!["localhost".to_string()]
+    };
+
+    let (deserialized, report) = serde_dynamic_typing::from_typed_with::<Config>(value, &Options::lenient())
+        .expect("Failed to deserialize config");
+    assert_eq!(deserialized, expected);
+
+    let mut coercions = report.coercions().to_vec();
+    coercions.sort();
+    assert_eq!(coercions, vec![
+        Coercion::StringToInteger, Coercion::IntegerToString, Coercion::IntegerToFloat, Coercion::FloatToInteger,
+        Coercion::IntegerToBool, Coercion::ValueToSequence
+    ]);
+}
+
+
+#[test]
+fn selective() {
+    let options = Options::new().coerce(Coercion::StringToFloat);
+    let (ratio, report) = serde_dynamic_typing::from_typed_with::<f64>(Utf8String::from("0.5").into(), &options)
+        .expect("Failed to deserialize float");
+    assert_eq!(ratio, 0.5);
+    assert_eq!(report.coercions(), &[Coercion::StringToFloat]);
+
+    // Other coercions are still rejected
+    let result = serde_dynamic_typing::from_typed_with::<u8>(Utf8String::from("7").into(), &options);
+    assert!(result.is_err());
+}
+
+
+#[test]
+fn strict_by_default() {
+    assert!(serde_dynamic_typing::from_typed::<u32>(Utf8String::from("42").into()).is_err());
+    assert!(serde_dynamic_typing::from_typed::<bool>(Integer::from(1u8).into()).is_err());
+    assert!(serde_dynamic_typing::from_typed::<Vec<u8>>(Integer::from(1u8).into()).is_err());
+}
+
+
+#[test]
+fn inexact() {
+    let options = Options::lenient();
+    assert!(serde_dynamic_typing::from_typed_with::<u8>(Float::from(1.5).into(), &options).is_err());
+    assert!(serde_dynamic_typing::from_typed_with::<u8>(Float::from(256.0).into(), &options).is_err());
+    assert!(serde_dynamic_typing::from_typed_with::<bool>(Integer::from(2u8).into(), &options).is_err());
+    assert!(serde_dynamic_typing::from_typed_with::<u8>(Utf8String::from("x").into(), &options).is_err());
+
+    let sequence = AnyValue::Sequence(Sequence::from_iter(vec![Integer::from(4u8).into()]));
+    let (value, report) = serde_dynamic_typing::from_typed_with::<Vec<u8>>(sequence, &options)
+        .expect("Failed to deserialize sequence");
+    assert_eq!(value, vec![4]);
+    assert!(report.coercions().is_empty());
+}
+
+
+#[test]
+fn failed_coercions_are_not_reported() {
+    let options = Options::lenient().collect_errors(true);
+    let (value, report) = serde_dynamic_typing::from_typed_with::<u8>(Utf8String::from("300").into(), &options)
+        .expect("Failed to deserialize integer");
+    assert_eq!(value, 0);
+    assert_eq!(report.errors().len(), 1);
+    assert!(report.coercions().is_empty());
+}
+
+
+#[test]
+fn float_to_string() {
+    let options = Options::new().coerce(Coercion::FloatToString);
+    for (float, expected) in [(1.0, "1.0"), (-0.5, "-0.5"), (1e300, "1e300")] {
+        let (string, _) = serde_dynamic_typing::from_typed_with::<String>(Float::from(float).into(), &options)
+            .expect("Failed to deserialize string");
+        assert_eq!(string, expected);
+    }
+}
@@ -0,0 +1,117 @@
+#[macro_use] extern crate serde_derive;
+mod common;
+
+use common::{ map, string };
+use serde::Deserialize;
+use serde_dynamic_typing::{
+    de::{ Deserializer, Options },
+    error::ErrorKind,
+    typing::{ Float, Integer, Sequence }
+};
+
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Server {
+    host: String,
+    port: u16
+}
+#[derive(Debug, PartialEq, Deserialize)]
+struct Config {
+    name: String,
+    servers: Vec<Server>,
+    retries: u8
+}
+
+
+#[test]
+fn all_errors() {
+    let servers = vec![
+        map(vec![("host", string("a")), ("port", Integer::from(80u8).into())]),
+        map(vec![("host", string("b")), ("port", Integer::from(-1i8).into())]),
+        map(vec![("host", Float::from(1.5).into()), ("port", string("443"))])
+    ];
+    let value = map(vec![
+        ("name", Integer::from(7u8).into()),
+        ("servers", Sequence::from(servers).into()),
+        ("retries", Integer::from(1000u16).into())
+    ]);
+
+    let errors = serde_dynamic_typing::from_typed_collecting::<Config>(value, &Options::new())
+        .expect_err("Deserialized an invalid config");
+    let paths: Vec<_> = errors.iter()
+        .map(|e| e.path().expect("Missing error path").to_string())
+        .collect();
+    assert_eq!(paths, vec!["$.name", "$.retries", "$.servers[1].port", "$.servers[2].host", "$.servers[2].port"]);
+    assert!(errors.iter().all(|e| *e.err() == ErrorKind::TypeError));
+}
+
+
+#[test]
+fn fatal_error() {
+    let incomplete = map(vec![("host", string("a"))]);
+    let value = map(vec![
+        ("name", Integer::from(7u8).into()),
+        ("servers", Sequence::from(vec![incomplete]).into()),
+        ("retries", Integer::from(3u8).into())
+    ]);
+
+    // The missing field stops the deserialization, but the previous errors are retained
+    let errors = serde_dynamic_typing::from_typed_collecting::<Config>(value, &Options::new())
+        .expect_err("Deserialized an invalid config");
+    assert_eq!(errors.len(), 2);
+    assert_eq!(*errors[1].err(), ErrorKind::NoSuchKey);
+    assert_eq!(errors[1].path().expect("Missing error path").to_string(), "$.servers[0].port");
+}
+
+
+#[test]
+fn substituted_defaults() {
+    let server = map(vec![("host", string("a")), ("port", string("http"))]);
+    let value = map(vec![
+        ("name", string("test")),
+        ("servers", Sequence::from(vec![server]).into()),
+        ("retries", Integer::from(3u8).into())
+    ]);
+
+    let options = Options::new().collect_errors(true);
+    let (config, report) = serde_dynamic_typing::from_typed_with::<Config>(value.clone(), &options)
+        .expect("Failed to deserialize config");
+    assert_eq!(config.servers, vec![Server { host: "a".to_string(), port: 0 }]);
+    assert_eq!(report.errors().len(), 1);
+
+    // Strict mode still fails immediately
+    assert!(serde_dynamic_typing::from_typed::<Config>(value).is_err());
+}
+
+
+#[test]
+fn public_deserializer() {
+    let server = map(vec![("host", string("a")), ("port", string("http"))]);
+    let value = map(vec![
+        ("name", string("test")),
+        ("servers", Sequence::from(vec![server]).into()),
+        ("retries", Integer::from(3u8).into())
+    ]);
+
+    let deserializer = Deserializer::with_options(value, Options::new().collect_errors(true));
+    let report = deserializer.report();
+    let config = Config::deserialize(deserializer).expect("Failed to deserialize config");
+    assert_eq!(config.servers, vec![Server { host: "a".to_string(), port: 0 }]);
+
+    let report = report.take();
+    assert_eq!(report.errors().len(), 1);
+    assert_eq!(report.errors()[0].path().map(ToString::to_string).as_deref(), Some("$.servers[0].port"));
+}
+
+
+#[test]
+fn valid() {
+    let value = map(vec![
+        ("name", string("test")),
+        ("servers", Sequence::from(Vec::new()).into()),
+        ("retries", Integer::from(3u8).into())
+    ]);
+    let config = serde_dynamic_typing::from_typed_collecting::<Config>(value, &Options::new())
+        .expect("Failed to deserialize config");
+    assert_eq!(config, Config { name: "test".to_string(), servers: Vec::new(), retries: 3 });
+}
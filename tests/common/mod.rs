@@ -0,0 +1,49 @@
+//! Helpers shared by the integration tests
+#![allow(dead_code)]
+
+use serde::{ Serialize, de::DeserializeOwned };
+use serde_dynamic_typing::{ de, ser, typing::{ AnyValue, Integer, Map, Sequence, Utf8String }, EnumRepr };
+use std::{ fmt::Debug, iter::FromIterator };
+
+
+/// Creates a string value
+pub fn string(value: &str) -> AnyValue {
+    AnyValue::Utf8String(Utf8String::from(value))
+}
+/// Creates a map with string keys from the given entries
+pub fn map(entries: Vec<(&str, AnyValue)>) -> AnyValue {
+    AnyValue::Map(Map::from_iter(entries.into_iter().map(|(key, value)| (string(key), value))))
+}
+/// Creates a sequence of integers
+pub fn integers<T>(values: &[T]) -> AnyValue where T: Copy + Into<Integer> {
+    AnyValue::Sequence(Sequence::from_iter(values.iter().map(|value| AnyValue::Integer((*value).into()))))
+}
+/// Serializes `value` and deserializes it again with the default options (see `roundtrip_with`)
+pub fn roundtrip<T>(value: &T) -> AnyValue where T: Debug + PartialEq + Serialize + DeserializeOwned {
+    roundtrip_with(value, &ser::Options::new(), &de::Options::new())
+}
+/// Serializes `value` and deserializes it again with the default, the strict, the compact and the externally tagged
+/// options (see `roundtrip_with`)
+pub fn roundtrip_all<T>(value: &T) where T: Debug + PartialEq + Serialize + DeserializeOwned {
+    let configurations = [
+        (ser::Options::new(), de::Options::new()),
+        (ser::Options::new().strict(true), de::Options::new().strict(true)),
+        (ser::Options::new().human_readable(false), de::Options::new().human_readable(false)),
+        (ser::Options::new().with_enum_repr(EnumRepr::External), de::Options::new().with_enum_repr(EnumRepr::External))
+    ];
+    for (ser_options, de_options) in configurations.iter() {
+        roundtrip_with(value, ser_options, de_options);
+    }
+}
+/// Serializes `value` with `ser_options`, deserializes it again with `de_options`, asserts that the deserialized value is
+/// equal to `value` and returns the intermediate representation
+pub fn roundtrip_with<T>(value: &T, ser_options: &ser::Options, de_options: &de::Options) -> AnyValue
+    where T: Debug + PartialEq + Serialize + DeserializeOwned
+{
+    let serialized = ser::to_typed_with(value, ser_options)
+        .unwrap_or_else(|e| panic!("Failed to serialize {:?}: {}", value, e));
+    let (deserialized, _) = de::from_typed_with::<T>(serialized.clone(), de_options)
+        .unwrap_or_else(|e| panic!("Failed to deserialize {:?}: {}", value, e));
+    assert_eq!(&deserialized, value);
+    serialized
+}
@@ -0,0 +1,136 @@
+#[macro_use] extern crate serde_derive;
+mod common;
+
+use common::roundtrip_all;
+use serde_dynamic_typing::{ from_typed, to_typed, typing::{ AnyValue, Enumeration } };
+use std::{
+    borrow::Cow,
+    net::{ IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr },
+    num::{ NonZeroI8, NonZeroI128, NonZeroU16, NonZeroU64, NonZeroUsize, Wrapping },
+    ops::{ Bound, Range, RangeInclusive },
+    path::PathBuf,
+    time::{ Duration, SystemTime, UNIX_EPOCH }
+};
+
+
+/// A unit struct
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Unit;
+
+
+/// A tuple struct without fields
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct EmptyTuple();
+
+
+/// A struct without fields
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct EmptyStruct {}
+
+
+/// An enum without variants
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Never {}
+
+
+#[test]
+fn time() {
+    roundtrip_all(&Duration::new(0, 0));
+    roundtrip_all(&Duration::new(u64::MAX, 999_999_999));
+    roundtrip_all(&UNIX_EPOCH);
+    roundtrip_all(&(UNIX_EPOCH + Duration::new(1_600_000_000, 42)));
+    roundtrip_all(&SystemTime::now());
+}
+
+
+#[test]
+fn net() {
+    roundtrip_all(&IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+    roundtrip_all(&IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)));
+    roundtrip_all(&Ipv4Addr::BROADCAST);
+    roundtrip_all(&"[2001:db8::1]:65535".parse::<SocketAddr>().expect("Invalid socket address?!"));
+}
+
+
+#[test]
+fn strings() {
+    roundtrip_all(&PathBuf::from("/tmp/test ü.txt"));
+    roundtrip_all(&Box::<str>::from("boxed"));
+    roundtrip_all(&Cow::<str>::Borrowed("borrowed"));
+    roundtrip_all(&Cow::<str>::Owned(String::from("owned")));
+    roundtrip_all(&'x');
+    roundtrip_all(&'🎉');
+    roundtrip_all(&String::new());
+}
+
+
+#[test]
+fn numbers() {
+    roundtrip_all(&NonZeroI8::new(i8::MIN).expect("Invalid non-zero value?!"));
+    roundtrip_all(&NonZeroI128::new(i128::MAX).expect("Invalid non-zero value?!"));
+    roundtrip_all(&NonZeroU16::new(7).expect("Invalid non-zero value?!"));
+    roundtrip_all(&NonZeroU64::new(u64::MAX).expect("Invalid non-zero value?!"));
+    roundtrip_all(&NonZeroUsize::new(1).expect("Invalid non-zero value?!"));
+    roundtrip_all(&Wrapping(u32::MAX));
+    roundtrip_all(&Wrapping(-1i64));
+    roundtrip_all(&(u128::MAX, i128::MIN));
+    roundtrip_all(&(f32::MIN_POSITIVE, f32::MAX, -0.1f32));
+    roundtrip_all(&(f64::EPSILON, f64::INFINITY, f64::NEG_INFINITY));
+
+    // Zero is rejected for non-zero types
+    from_typed::<NonZeroU16>(to_typed(&0u16).expect("Failed to serialize zero")).expect_err("Deserialized zero?!");
+}
+
+
+#[test]
+fn ranges() {
+    roundtrip_all(&Range { start: 1u8, end: 7 });
+    roundtrip_all(&RangeInclusive::new(-7i32, 7));
+    roundtrip_all(&Bound::Included(7u8));
+    roundtrip_all(&Bound::Excluded(String::from("end")));
+    roundtrip_all(&Bound::<u8>::Unbounded);
+}
+
+
+#[test]
+fn collections() {
+    roundtrip_all(&[1u8, 2, 3, 4]);
+    roundtrip_all(&[[0u16; 3]; 2]);
+    roundtrip_all(&<[String; 0]>::default());
+    roundtrip_all(&Vec::<u8>::new());
+    roundtrip_all(&vec![Some(1u8), None]);
+}
+
+
+#[test]
+fn options() {
+    roundtrip_all(&Some(7u8));
+    roundtrip_all(&None::<u8>);
+    roundtrip_all(&Some(Some(7u8)));
+    roundtrip_all(&None::<Option<u8>>);
+
+    // Like most self-describing formats, `Some(None)` is indistinguishable from `None`
+    let serialized = to_typed(&Some(None::<u8>)).expect("Failed to serialize nested option");
+    assert_eq!(serialized, AnyValue::None);
+    assert_eq!(from_typed::<Option<Option<u8>>>(serialized).expect("Failed to deserialize nested option"), None);
+}
+
+
+#[test]
+fn units() {
+    roundtrip_all(&());
+    roundtrip_all(&Unit);
+    roundtrip_all(&EmptyTuple());
+    roundtrip_all(&EmptyStruct {});
+    roundtrip_all(&((), Unit, EmptyTuple()));
+}
+
+
+#[test]
+fn empty_enum() {
+    roundtrip_all(&None::<Never>);
+    roundtrip_all(&Ok::<u8, Never>(7));
+
+    let value = AnyValue::Enum(Enumeration::new("Variant"));
+    from_typed::<Never>(value).expect_err("Deserialized an empty enum?!");
+}
@@ -0,0 +1,85 @@
+use serde_dynamic_typing::{
+    repr, tree,
+    typing::{ AnyValue, Boolean, Enumeration, Integer, Map, Sequence, Utf8String },
+    EnumRepr
+};
+use std::{ cmp::Ordering, iter::FromIterator };
+
+
+/// The nesting depth of the test values (deep enough to overflow the stack of a test thread if processed recursively)
+const DEPTH: usize = 100_000;
+
+
+/// Creates a value with `depth` levels that alternately nests sequences, maps and enums around `leaf`
+fn nested(depth: usize, leaf: u8) -> AnyValue {
+    (0..depth).fold(Integer::from(leaf).into(), |nested, level| match level % 3 {
+        0 => AnyValue::Sequence(Sequence::new([nested])),
+        1 => AnyValue::Map(Map::from_iter(vec![(Utf8String::from("key").into(), nested)])),
+        _ => AnyValue::Enum(Enumeration::with_value("Variant", nested))
+    })
+}
+
+
+#[test]
+fn deep_value() {
+    let value = nested(DEPTH, 0);
+
+    // Clone and compare
+    let cloned = value.clone();
+    assert!(value == cloned, "Cloned value is not equal to the original value?!");
+    assert_eq!(value.cmp(&cloned), Ordering::Equal);
+
+    // Compare against a value with a different leaf
+    let greater = nested(DEPTH, 1);
+    assert!(value != greater, "Values with different leaves are equal?!");
+    assert_eq!(value.cmp(&greater), Ordering::Less);
+    assert_eq!(greater.cmp(&value), Ordering::Greater);
+
+    // Drop all values
+    drop((value, cloned, greater));
+}
+
+
+#[test]
+fn deep_tree() {
+    // Merge values that only differ in their leaves
+    let mut value = nested(DEPTH, 0);
+    let changed = tree::merge(&mut value, nested(DEPTH, 1));
+    assert_eq!(changed.len(), 1);
+    assert!(value == nested(DEPTH, 1), "Merged value is not equal to the update?!");
+
+    // Fill the value with defaults that only differ in their leaves
+    let value = tree::fill_defaults(value, &nested(DEPTH, 0));
+    assert!(value == nested(DEPTH, 1), "Existing values have been replaced by defaults?!");
+
+    // Rebuild the value
+    let encoded = repr::encode_enums(value, &EnumRepr::External).expect("Failed to encode deep enums");
+    let stringified = tree::stringify_keys(encoded).expect("Failed to stringify deep keys");
+    drop(tree::collapse_bytes(stringified));
+}
+
+
+#[test]
+fn deep_debug() {
+    let formatted = format!("{:?}", nested(DEPTH, 0));
+    assert!(formatted.contains(".."), "Deeply nested values are not elided?!");
+
+    let shallow = AnyValue::Sequence(Sequence::new([AnyValue::Enum(Enumeration::new("Unit"))]));
+    assert_eq!(
+        format!("{:?}", shallow),
+        r#"Sequence(Sequence { inner: [Enum(Enumeration { variant: Utf8String { inner: "Unit" }, value: None })] })"#
+    );
+}
+
+
+#[test]
+fn shallow_ordering() {
+    // Sequences are compared lexicographically like their derived implementation
+    let short = AnyValue::Sequence(Sequence::new([AnyValue::from(Boolean::from(true))]));
+    let long = AnyValue::Sequence(Sequence::new([AnyValue::from(Boolean::from(true)), AnyValue::None]));
+    assert_eq!(short.cmp(&long), Ordering::Less);
+
+    // Values of different kinds are ordered by their declaration order
+    assert_eq!(AnyValue::from(Boolean::from(true)).cmp(&AnyValue::None), Ordering::Less);
+    assert_eq!(AnyValue::None.cmp(&short), Ordering::Greater);
+}
@@ -0,0 +1,86 @@
+#[macro_use] extern crate serde_derive;
+mod common;
+
+use common::{ map, string };
+use serde_dynamic_typing::{
+    de,
+    error::ErrorKind,
+    typing::{ AnyValue, Enumeration, Integer, Sequence }
+};
+
+
+/// A server config
+#[derive(Debug, PartialEq, Deserialize)]
+struct Server {
+    host: String,
+    port: u16,
+    tags: Vec<String>,
+    mode: Mode,
+    limits: Limits
+}
+/// A server mode
+#[derive(Debug, PartialEq, Deserialize)]
+enum Mode {
+    Local,
+    Remote { timeout: u32, retries: u8 }
+}
+/// The server limits
+#[derive(Debug, PartialEq, Deserialize)]
+struct Limits {
+    connections: u32,
+    requests: u32
+}
+
+
+/// Creates the default server config
+fn defaults() -> AnyValue {
+    map(vec![
+        ("host", string("localhost")),
+        ("port", Integer::from(80u8).into()),
+        ("tags", AnyValue::Sequence(Sequence::new([string("default")]))),
+        ("mode", AnyValue::Enum(Enumeration::with_value("Remote", map(vec![
+            ("timeout", Integer::from(30u8).into()),
+            ("retries", Integer::from(3u8).into())
+        ])))),
+        ("limits", map(vec![("connections", Integer::from(16u8).into()), ("requests", Integer::from(1024u16).into())]))
+    ])
+}
+
+
+#[test]
+fn fill() {
+    let value = map(vec![
+        ("host", string("example.org")),
+        ("port", AnyValue::None),
+        ("tags", AnyValue::Sequence(Sequence::new([string("a"), string("b")]))),
+        ("mode", AnyValue::Enum(Enumeration::with_value("Remote", map(vec![("retries", Integer::from(5u8).into())])))),
+        ("limits", map(vec![("requests", Integer::from(64u8).into())]))
+    ]);
+    let server: Server = de::from_typed_with_defaults(value, &defaults()).expect("Failed to deserialize");
+    assert_eq!(server, Server {
+        host: String::from("example.org"), port: 80, tags: vec![String::from("a"), String::from("b")],
+        mode: Mode::Remote { timeout: 30, retries: 5 }, limits: Limits { connections: 16, requests: 64 }
+    });
+
+    // An empty value is filled completely
+    let server: Server = de::from_typed_with_defaults(map(Vec::new()), &defaults()).expect("Failed to deserialize");
+    assert_eq!(server.host, "localhost");
+    assert_eq!(server.tags, [String::from("default")]);
+}
+
+
+#[test]
+fn other_variant() {
+    let value = map(vec![("mode", AnyValue::Enum(Enumeration::new("Local")))]);
+    let server: Server = de::from_typed_with_defaults(value, &defaults()).expect("Failed to deserialize");
+    assert_eq!(server.mode, Mode::Local);
+}
+
+
+#[test]
+fn incomplete_defaults() {
+    let defaults = map(vec![("host", string("localhost"))]);
+    let value = map(vec![("port", Integer::from(80u8).into())]);
+    let error = de::from_typed_with_defaults::<Server>(value, &defaults).expect_err("Deserialized incomplete value?!");
+    assert_eq!(error.err(), &ErrorKind::NoSuchKey);
+}
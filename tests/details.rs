@@ -0,0 +1,49 @@
+use serde_dynamic_typing::{
+    error::{ Details, ErrorKind },
+    typing::{ AnyValue, Integer, Kind, Sequence, Utf8String }
+};
+use std::iter::FromIterator;
+
+
+#[test]
+fn type_mismatch() {
+    let value = AnyValue::Sequence(Sequence::from_iter((0..1000u32).map(|i| Integer::from(i).into())));
+    let error = serde_dynamic_typing::from_typed::<String>(value).expect_err("Deserialized a sequence as string");
+
+    assert_eq!(*error.err(), ErrorKind::TypeError);
+    assert_eq!(error.expected(), Some(Kind::Utf8String));
+    assert_eq!(error.found(), Some(Kind::Sequence));
+
+    // The preview must be truncated
+    let preview = error.preview().expect("Missing value preview");
+    assert!(preview.starts_with("[0, 1, 2, "));
+    assert!(preview.ends_with('…'));
+    assert!(preview.chars().count() <= 49);
+    assert!(error.desc().len() < 100);
+}
+
+
+#[test]
+fn out_of_range() {
+    let error = serde_dynamic_typing::from_typed::<i8>(Integer::from(300u16).into())
+        .expect_err("Deserialized 300 as i8");
+
+    assert_eq!(*error.err(), ErrorKind::TypeError);
+    assert_eq!(error.expected(), None);
+    assert_eq!(error.details(), Some(&Details::Range {
+        value: Integer::from(300u16),
+        min: Integer::from(i8::MIN),
+        max: Integer::from(i8::MAX),
+        target: "i8"
+    }));
+    assert_eq!(error.desc(), "Cannot represent 300 as `i8` (expected -128..=127)");
+}
+
+
+#[test]
+fn without_details() {
+    let error = serde_dynamic_typing::from_typed::<char>(Utf8String::from("ab").into())
+        .expect_err("Deserialized a multichar string as char");
+    assert_eq!(error.details(), None);
+    assert_eq!(error.found(), None);
+}
@@ -0,0 +1,107 @@
+#[macro_use] extern crate serde_derive;
+
+use serde_dynamic_typing::{
+    error::ErrorKind,
+    field,
+    typing::Kind
+};
+use std::collections::BTreeMap;
+
+
+/// A server config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Server {
+    host: String,
+    port: u16,
+    mode: Mode
+}
+/// A server mode
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Mode {
+    Local,
+    Remote { timeout: u32 }
+}
+/// A config with nested servers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Config {
+    name: String,
+    servers: Vec<Server>,
+    ids: BTreeMap<u32, String>
+}
+
+
+/// Creates a config
+fn config() -> Config {
+    let servers = vec![
+        Server { host: String::from("localhost"), port: 80, mode: Mode::Local },
+        Server { host: String::from("example.org"), port: 443, mode: Mode::Remote { timeout: 30 } }
+    ];
+    let ids = BTreeMap::from([(7, String::from("seven"))]);
+    Config { name: String::from("test"), servers, ids }
+}
+
+
+#[test]
+fn get() {
+    let config = config();
+    let port: u16 = field::get_field(&config, "servers.1.port").expect("Failed to get field");
+    assert_eq!(port, 443);
+    let timeout: u32 = field::get_field(&config, "servers.1.mode.Remote.timeout").expect("Failed to get field");
+    assert_eq!(timeout, 30);
+    let id: String = field::get_field(&config, "ids.7").expect("Failed to get field");
+    assert_eq!(id, "seven");
+    let server: Server = field::get_field(&config, "servers.0").expect("Failed to get field");
+    assert_eq!(server, config.servers[0]);
+    let root: Config = field::get_field(&config, "").expect("Failed to get field");
+    assert_eq!(root, config);
+}
+
+
+#[test]
+fn get_invalid() {
+    let config = config();
+
+    // Missing segments
+    let error = field::get_field::<_, u16>(&config, "servers.1.prot").expect_err("Got missing field?!");
+    assert_eq!(error.err(), &ErrorKind::NoSuchKey);
+    assert_eq!(error.path().expect("Missing error path?!").to_string(), "$.servers[1].prot");
+    let error = field::get_field::<_, u16>(&config, "servers.2.port").expect_err("Got missing element?!");
+    assert_eq!(error.err(), &ErrorKind::NoSuchKey);
+    assert_eq!(error.path().expect("Missing error path?!").to_string(), "$.servers[2]");
+    let error = field::get_field::<_, u32>(&config, "servers.0.mode.Remote.timeout").expect_err("Got wrong variant?!");
+    assert_eq!(error.err(), &ErrorKind::NoSuchKey);
+    assert_eq!(error.path().expect("Missing error path?!").to_string(), "$.servers[0].mode");
+
+    // Descending into a scalar
+    let error = field::get_field::<_, u16>(&config, "name.first").expect_err("Descended into a string?!");
+    assert_eq!(error.err(), &ErrorKind::TypeError);
+    assert_eq!((error.expected(), error.found()), (Some(Kind::Map), Some(Kind::Utf8String)));
+    assert_eq!(error.path().expect("Missing error path?!").to_string(), "$.name");
+
+    // A field of the wrong type
+    let error = field::get_field::<_, u16>(&config, "servers.0.host").expect_err("Got string as integer?!");
+    assert_eq!((error.expected(), error.found()), (Some(Kind::Integer), Some(Kind::Utf8String)));
+    assert_eq!(error.path().expect("Missing error path?!").to_string(), "$.servers[0].host");
+
+    // An empty segment
+    let error = field::get_field::<_, u16>(&config, "servers..port").expect_err("Accepted empty segment?!");
+    assert_eq!(error.err(), &ErrorKind::InvalidData);
+}
+
+
+#[test]
+fn set() {
+    let mut config = config();
+    field::set_field(&mut config, "servers.0.port", 8080u16).expect("Failed to set field");
+    field::set_field(&mut config, "servers.1.mode", Mode::Local).expect("Failed to set field");
+    assert_eq!(config.servers[0].port, 8080);
+    assert_eq!(config.servers[1].mode, Mode::Local);
+
+    // Invalid updates leave the value untouched
+    let expected = config.clone();
+    let error = field::set_field(&mut config, "servers.0.port", "8080").expect_err("Set string as integer?!");
+    assert_eq!((error.expected(), error.found()), (Some(Kind::Integer), Some(Kind::Utf8String)));
+    assert_eq!(error.path().expect("Missing error path?!").to_string(), "$.servers[0].port");
+    field::set_field(&mut config, "servers.0.prot", 8080u16).expect_err("Set missing field?!");
+    assert_eq!(config, expected);
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use common::roundtrip_with;
+use serde_dynamic_typing::{ de, ser, typing::{ AnyValue, Utf8String } };
+use std::net::{ IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr };
+
+
+#[test]
+fn defaults() {
+    assert!(ser::Options::new().is_human_readable());
+    assert!(de::Options::new().is_human_readable());
+
+    // The defaults of both sides are consistent
+    let address = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    let serialized = serde_dynamic_typing::to_typed(&address).expect("Failed to serialize address");
+    assert_eq!(serialized, AnyValue::Utf8String(Utf8String::from("127.0.0.1")));
+    let deserialized: IpAddr = serde_dynamic_typing::from_typed(serialized).expect("Failed to deserialize address");
+    assert_eq!(deserialized, address);
+}
+
+
+#[test]
+fn ip_addr() {
+    let addresses = [IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1)), IpAddr::V6(Ipv6Addr::LOCALHOST)];
+    let ser_compact = ser::Options::new().human_readable(false);
+    let de_compact = de::Options::new().human_readable(false);
+    for address in addresses.iter() {
+        let readable = roundtrip_with(address, &ser::Options::new(), &de::Options::new());
+        assert_eq!(readable, AnyValue::Utf8String(Utf8String::new(address)));
+
+        let compact = roundtrip_with(address, &ser_compact, &de_compact);
+        assert!(matches!(compact, AnyValue::Enum(_)), "Unexpected compact representation?!");
+    }
+}
+
+
+#[test]
+fn socket_addr() {
+    let addresses: [SocketAddr; 2] = [
+        "192.168.0.1:8080".parse().expect("Invalid socket address?!"),
+        "[::1]:443".parse().expect("Invalid socket address?!")
+    ];
+    let ser_compact = ser::Options::new().human_readable(false);
+    let de_compact = de::Options::new().human_readable(false);
+    for address in addresses.iter() {
+        let readable = roundtrip_with(address, &ser::Options::new(), &de::Options::new());
+        assert_eq!(readable, AnyValue::Utf8String(Utf8String::new(address)));
+
+        let compact = roundtrip_with(address, &ser_compact, &de_compact);
+        assert!(matches!(compact, AnyValue::Enum(_)), "Unexpected compact representation?!");
+    }
+}
+
+
+#[test]
+fn mismatch() {
+    // A compact value cannot be read by a human readable deserializer
+    let compact = ser::to_typed_with(&Ipv4Addr::LOCALHOST, &ser::Options::new().human_readable(false))
+        .expect("Failed to serialize address");
+    de::from_typed_with::<Ipv4Addr>(compact, &de::Options::new()).expect_err("Deserialized compact address?!");
+}
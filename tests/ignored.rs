@@ -0,0 +1,106 @@
+#[macro_use] extern crate serde_derive;
+mod common;
+
+use common::{ map, string };
+use serde::de::{ Deserialize, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor };
+use serde_dynamic_typing::{
+    de::{ self, Options },
+    typing::{ AnyValue, Integer, Sequence }
+};
+use std::fmt::{ self, Formatter };
+
+
+/// A server config
+#[derive(Debug, PartialEq, Deserialize)]
+struct Server {
+    host: String,
+    port: u16
+}
+/// A config with nested servers
+#[derive(Debug, PartialEq, Deserialize)]
+struct Config {
+    name: String,
+    servers: Vec<Server>
+}
+
+
+/// A type that only reads the first element of a sequence and the first entry of a map
+#[derive(Debug, PartialEq)]
+struct First(u8);
+impl<'de> Deserialize<'de> for First {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        /// The visitor for `First`
+        struct FirstVisitor;
+        impl<'de> Visitor<'de> for FirstVisitor {
+            type Value = First;
+
+            fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+                write!(f, "a sequence or map")
+            }
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error> where A: SeqAccess<'de> {
+                let first = seq.next_element()?.unwrap_or_default();
+                Ok(First(first))
+            }
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error> where A: MapAccess<'de> {
+                let _key: Option<IgnoredAny> = map.next_key()?;
+                Ok(First(0))
+            }
+        }
+        deserializer.deserialize_any(FirstVisitor)
+    }
+}
+
+
+#[test]
+fn unknown_fields() {
+    let value = map(vec![
+        ("name", string("test")),
+        ("nmae", string("typo")),
+        ("servers", AnyValue::Sequence(Sequence::new([
+            map(vec![("host", string("localhost")), ("port", Integer::from(80u8).into())]),
+            map(vec![
+                ("host", string("localhost")), ("port", Integer::from(443u16).into()),
+                ("tls", map(vec![("cert", string("cert.pem")), ("key", string("key.pem"))]))
+            ])
+        ])))
+    ]);
+    let (config, ignored) = de::from_typed_tracking::<Config>(value, &Options::new()).expect("Failed to deserialize");
+    assert_eq!(config.name, "test");
+    assert_eq!(config.servers.len(), 2);
+
+    // Nested values of ignored values are not reported separately
+    let ignored: Vec<_> = ignored.iter().map(ToString::to_string).collect();
+    assert_eq!(ignored, ["$.nmae", "$.servers[1].tls"]);
+}
+
+
+#[test]
+fn unvisited() {
+    let value = AnyValue::Sequence(Sequence::new([
+        Integer::from(1u8).into(), Integer::from(2u8).into(), Integer::from(3u8).into()
+    ]));
+    let (first, ignored) = de::from_typed_tracking::<First>(value, &Options::new()).expect("Failed to deserialize");
+    assert_eq!(first, First(1));
+    let ignored: Vec<_> = ignored.iter().map(ToString::to_string).collect();
+    assert_eq!(ignored, ["$[1]", "$[2]"]);
+
+    // A pending value and the trailing entries
+    let value = map(vec![("a", string("a")), ("b", string("b"))]);
+    let (_, ignored) = de::from_typed_tracking::<First>(value, &Options::new()).expect("Failed to deserialize");
+    let ignored: Vec<_> = ignored.iter().map(ToString::to_string).collect();
+    assert_eq!(ignored, ["$.a", "$.b"]);
+}
+
+
+#[test]
+fn untracked() {
+    let value = map(vec![("host", string("localhost")), ("port", Integer::from(80u8).into()), ("tls", string("on"))]);
+    let (_, report) = de::from_typed_with::<Server>(value.clone(), &Options::new()).expect("Failed to deserialize");
+    assert!(report.ignored().is_empty());
+
+    // Track via the options
+    let options = Options::new().track_ignored(true);
+    let (_, report) = de::from_typed_with::<Server>(value, &options).expect("Failed to deserialize");
+    assert_eq!(report.ignored().len(), 1);
+    assert_eq!(report.ignored()[0].to_string(), "$.tls");
+}
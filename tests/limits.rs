@@ -0,0 +1,102 @@
+#[macro_use] extern crate serde_derive;
+
+use serde::de::{ Deserialize, IgnoredAny };
+use serde_dynamic_typing::{
+    de, ser,
+    error::ErrorKind,
+    typing::{ AnyValue, Bytes, Integer, Sequence, Utf8String },
+    Limits
+};
+use std::iter::FromIterator;
+
+
+/// A recursive type
+#[derive(Debug, Serialize)]
+enum Nested {
+    Leaf,
+    Node(Box<Nested>)
+}
+impl Nested {
+    /// Creates a value with `depth` nested nodes
+    pub fn new(depth: usize) -> Self {
+        (0..depth).fold(Self::Leaf, |nested, _| Self::Node(Box::new(nested)))
+    }
+}
+
+
+/// Creates a nested sequence with `depth` levels
+fn nested_sequence(depth: usize) -> AnyValue {
+    (0..depth).fold(AnyValue::None, |nested, _| AnyValue::Sequence(Sequence::new([nested])))
+}
+
+
+#[test]
+fn max_depth() {
+    let limits = Limits::new().max_depth(32);
+
+    // Serialize
+    let options = ser::Options::new().with_limits(limits);
+    ser::to_typed_with(&Nested::new(8), &options).expect("Failed to serialize nested value");
+    let error = ser::to_typed_with(&Nested::new(1000), &options).expect_err("Serialized too deeply nested value?!");
+    assert_eq!(error.err(), &ErrorKind::LimitExceeded);
+
+    // Deserialize
+    let options = de::Options::new().with_limits(limits);
+    de::from_typed_with::<Vec<Vec<()>>>(nested_sequence(2), &options).expect("Failed to deserialize nested value");
+    let error = de::from_typed_with::<IgnoredAny>(nested_sequence(1000), &options)
+        .expect_err("Deserialized too deeply nested value?!");
+    assert_eq!(error.err(), &ErrorKind::LimitExceeded);
+}
+
+
+#[test]
+fn max_nodes() {
+    let limits = Limits::new().max_nodes(4);
+
+    ser::to_typed_with(&vec![0; 3], &ser::Options::new().with_limits(limits)).expect("Failed to serialize sequence");
+    let error = ser::to_typed_with(&vec![0; 4], &ser::Options::new().with_limits(limits))
+        .expect_err("Serialized too many values?!");
+    assert_eq!(error.err(), &ErrorKind::LimitExceeded);
+
+    let sequence = AnyValue::Sequence(Sequence::from_iter((0..4u8).map(|i| Integer::from(i).into())));
+    let error = de::from_typed_with::<Vec<u8>>(sequence, &de::Options::new().with_limits(limits))
+        .expect_err("Deserialized too many values?!");
+    assert_eq!(error.err(), &ErrorKind::LimitExceeded);
+    assert_eq!(error.path().expect("Missing error location").to_string(), "$[3]");
+
+    // Absent optional values are counted as well
+    let deserializer = de::Deserializer::new(AnyValue::None).with_limits(Limits::new().max_nodes(0));
+    let error = Option::<u8>::deserialize(deserializer).expect_err("Deserialized too many values?!");
+    assert_eq!(error.err(), &ErrorKind::LimitExceeded);
+}
+
+
+#[test]
+fn max_lengths() {
+    let limits = Limits::new().max_string_len(4).max_bytes_len(4).max_collection_len(4);
+    let (ser_options, de_options) = (ser::Options::new().with_limits(limits), de::Options::new().with_limits(limits));
+
+    // Strings
+    ser::to_typed_with("four", &ser_options).expect("Failed to serialize string");
+    let error = ser::to_typed_with("fives", &ser_options).expect_err("Serialized too long string?!");
+    assert_eq!(error.err(), &ErrorKind::LimitExceeded);
+    let error = de::from_typed_with::<String>(Utf8String::from("fives").into(), &de_options)
+        .expect_err("Deserialized too long string?!");
+    assert_eq!(error.err(), &ErrorKind::LimitExceeded);
+
+    // Bytes
+    let error = ser::to_typed_with(serde_bytes::Bytes::new(b"fives"), &ser_options)
+        .expect_err("Serialized too long bytes?!");
+    assert_eq!(error.err(), &ErrorKind::LimitExceeded);
+    let error = de::from_typed_with::<serde_bytes::ByteBuf>(Bytes::from(b"fives".to_vec()).into(), &de_options)
+        .expect_err("Deserialized too long bytes?!");
+    assert_eq!(error.err(), &ErrorKind::LimitExceeded);
+
+    // Collections
+    ser::to_typed_with(&vec![(); 4], &ser_options).expect("Failed to serialize sequence");
+    let error = ser::to_typed_with(&vec![(); 5], &ser_options).expect_err("Serialized too long sequence?!");
+    assert_eq!(error.err(), &ErrorKind::LimitExceeded);
+    let sequence = AnyValue::Sequence(Sequence::from_iter(vec![AnyValue::None; 5]));
+    let error = de::from_typed_with::<Vec<()>>(sequence, &de_options).expect_err("Deserialized too long sequence?!");
+    assert_eq!(error.err(), &ErrorKind::LimitExceeded);
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use common::string;
+use serde_dynamic_typing::{
+    de::{ self, Coercion, Options },
+    error::ErrorKind,
+    tree,
+    typing::{ AnyValue, Boolean, Float, Integer, Map, Sequence }
+};
+use std::{ collections::BTreeMap, iter::FromIterator };
+
+
+/// Creates a sequence of `[key, value]`-pairs
+fn pairs(pairs: Vec<(AnyValue, AnyValue)>) -> AnyValue {
+    let pairs = pairs.into_iter().map(|(key, value)| AnyValue::Sequence(Sequence::new([key, value])));
+    AnyValue::Sequence(Sequence::from_iter(pairs))
+}
+
+
+#[test]
+fn pairs_to_map() {
+    let value = pairs(vec![
+        (Integer::from(1u8).into(), string("one")),
+        (Integer::from(2u8).into(), string("two"))
+    ]);
+    let expected = BTreeMap::from_iter(vec![(1u8, String::from("one")), (2, String::from("two"))]);
+
+    // Sequences of pairs are only accepted if enabled
+    de::from_typed::<BTreeMap<u8, String>>(value.clone()).expect_err("Deserialized sequence as map?!");
+    let options = Options::new().coerce(Coercion::PairsToMap);
+    let (map, report) = de::from_typed_with::<BTreeMap<u8, String>>(value, &options)
+        .expect("Failed to deserialize pairs as map");
+    assert_eq!(map, expected);
+    assert_eq!(report.coercions(), &[Coercion::PairsToMap]);
+
+    // Duplicate keys are rejected
+    let value = pairs(vec![(Integer::from(1u8).into(), string("one")), (Integer::from(1u8).into(), string("uno"))]);
+    let error = de::from_typed_with::<BTreeMap<u8, String>>(value, &options)
+        .expect_err("Deserialized pairs with duplicate keys?!");
+    assert_eq!(error.err(), &ErrorKind::InvalidData);
+}
+
+
+#[test]
+fn parse_string_keys() {
+    let value = AnyValue::Map(Map::from_iter(vec![
+        (string("-7"), string("minus seven")),
+        (string("42"), string("42"))
+    ]));
+    let options = Options::new().coerce(Coercion::ParseStringKeys);
+
+    // Integer keys
+    let (map, _) = de::from_typed_with::<BTreeMap<i32, String>>(value.clone(), &options)
+        .expect("Failed to parse integer keys");
+    assert_eq!(map, BTreeMap::from_iter(vec![(-7, String::from("minus seven")), (42, String::from("42"))]));
+    de::from_typed::<BTreeMap<i32, String>>(value.clone()).expect_err("Parsed string keys by default?!");
+
+    // Values are not affected
+    de::from_typed_with::<BTreeMap<String, i32>>(value.clone(), &options).expect_err("Parsed string values?!");
+    let (map, report) = de::from_typed_with::<BTreeMap<String, String>>(value, &options)
+        .expect("Failed to deserialize string keys");
+    assert_eq!(map.len(), 2);
+    assert!(report.coercions().is_empty());
+
+    // Boolean keys
+    let value = AnyValue::Map(Map::from_iter(vec![(string("true"), string("yes")), (string("false"), string("no"))]));
+    let (map, _) = de::from_typed_with::<BTreeMap<bool, String>>(value, &options).expect("Failed to parse bool keys");
+    assert_eq!(map.get(&true).map(String::as_str), Some("yes"));
+}
+
+
+#[test]
+fn stringify_keys() {
+    let value = AnyValue::Map(Map::from_iter(vec![
+        (Integer::from(-1i8).into(), Boolean::from(true).into()),
+        (Boolean::from(false).into(), AnyValue::Map(Map::from_iter(vec![(Float::from(0.5).into(), AnyValue::None)]))),
+        (string("name"), AnyValue::None)
+    ]));
+    let stringified = tree::stringify_keys(value).expect("Failed to stringify keys");
+    assert_eq!(stringified, AnyValue::Map(Map::from_iter(vec![
+        (string("-1"), Boolean::from(true).into()),
+        (string("false"), AnyValue::Map(Map::from_iter(vec![(string("0.5"), AnyValue::None)]))),
+        (string("name"), AnyValue::None)
+    ])));
+
+    // Complex keys and colliding keys are rejected
+    let value = AnyValue::Map(Map::from_iter(vec![(AnyValue::Sequence(Sequence::default()), AnyValue::None)]));
+    let error = tree::stringify_keys(value).expect_err("Stringified sequence key?!");
+    assert_eq!(error.err(), &ErrorKind::TypeError);
+    let value = AnyValue::Map(Map::from_iter(vec![
+        (Integer::from(1u8).into(), AnyValue::None),
+        (string("1"), AnyValue::None)
+    ]));
+    let error = tree::stringify_keys(value).expect_err("Stringified colliding keys?!");
+    assert_eq!(error.err(), &ErrorKind::InvalidData);
+}
@@ -0,0 +1,138 @@
+#[macro_use] extern crate serde_derive;
+
+use serde_dynamic_typing::{
+    error::ErrorKind,
+    mapping::Mapping,
+    typing::{ AnyValue, Integer, Utf8String }
+};
+use std::{ convert::TryFrom, sync::OnceLock, thread };
+
+
+/// A server DTO
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ServerDto {
+    hostname: String,
+    port: u16,
+    secret: String,
+    state: StateDto,
+    tls_cert: Option<String>
+}
+/// A server state DTO
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum StateDto {
+    Up,
+    Down
+}
+
+
+/// A server domain type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Server {
+    host: String,
+    port: u16,
+    retries: u8,
+    state: State,
+    address: String,
+    tls: Tls
+}
+/// A server state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum State {
+    Online,
+    Offline
+}
+/// The TLS config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Tls {
+    cert: Option<String>
+}
+
+
+/// Creates a server DTO
+fn dto() -> ServerDto {
+    ServerDto {
+        hostname: String::from("localhost"), port: 80, secret: String::from("secret"), state: StateDto::Down,
+        tls_cert: Some(String::from("cert.pem"))
+    }
+}
+/// Gets the map entry `key` of `value` or `AnyValue::None`
+fn entry(value: &AnyValue, key: &str) -> AnyValue {
+    match value {
+        AnyValue::Map(map) => map.get(&AnyValue::Utf8String(Utf8String::from(key))).cloned().unwrap_or(AnyValue::None),
+        _ => AnyValue::None
+    }
+}
+/// Creates the mapping from `ServerDto` to `Server`
+fn mapping() -> Mapping {
+    Mapping::new()
+        .rename("hostname", "host")
+        .drop_field("secret")
+        .with_default("retries", AnyValue::Integer(Integer::from(3u8)))
+        .rename_variants("state", [("Up", "Online"), ("Down", "Offline")])
+        .move_field("tls_cert", "tls.cert")
+        .compute("address", |value| {
+            let host = Utf8String::try_from(entry(value, "host"))?;
+            let port = u16::try_from(Integer::try_from(entry(value, "port"))?)?;
+            let address = format!("{}:{}", host.as_str(), port);
+            Ok(AnyValue::Utf8String(Utf8String::from(address.as_str())))
+        })
+}
+
+
+#[test]
+fn convert() {
+    let server: Server = mapping().convert(&dto()).expect("Failed to map value");
+    assert_eq!(server, Server {
+        host: String::from("localhost"), port: 80, retries: 3, state: State::Offline,
+        address: String::from("localhost:80"), tls: Tls { cert: Some(String::from("cert.pem")) }
+    });
+}
+
+
+#[test]
+fn shared() {
+    static MAPPING: OnceLock<Mapping> = OnceLock::new();
+    let mapping = MAPPING.get_or_init(mapping);
+
+    let server = thread::spawn(move || mapping.convert::<_, Server>(&dto()).expect("Failed to map value"))
+        .join().expect("Failed to map value in another thread");
+    assert_eq!(server.address, "localhost:80");
+}
+
+
+#[test]
+fn dry_run() {
+    let report = mapping().dry_run::<_, Server>(&dto()).expect("Failed to perform dry-run");
+    assert!(report.is_complete(), "Incomplete mapping?!");
+
+    // Omit some rules
+    let mapping = Mapping::new()
+        .rename_variants("state", [("Up", "Online"), ("Down", "Offline")])
+        .move_field("tls_cert", "tls.cert");
+    let report = mapping.dry_run::<_, Server>(&dto()).expect("Failed to perform dry-run");
+    let unmapped: Vec<_> = report.unmapped().iter().map(ToString::to_string).collect();
+    assert_eq!(unmapped, ["$.hostname", "$.secret"]);
+    let missing: Vec<_> = report.missing().iter().map(ToString::to_string).collect();
+    assert_eq!(missing, ["$.host", "$.retries", "$.address"]);
+
+    // Missing nested structs are only reported once
+    let mapping = Mapping::new().rename_variants("state", [("Down", "Offline")]).drop_field("tls_cert");
+    let report = mapping.dry_run::<_, Server>(&dto()).expect("Failed to perform dry-run");
+    let missing: Vec<_> = report.missing().iter().map(ToString::to_string).collect();
+    assert_eq!(missing, ["$.host", "$.retries", "$.address", "$.tls"]);
+}
+
+
+#[test]
+fn invalid() {
+    // A missing variant mapping
+    let unknown = mapping().rename_variants("state", [("Offline", "Unknown")]);
+    let error = unknown.convert::<_, Server>(&dto()).expect_err("Mapped unknown variant?!");
+    assert_eq!(error.err(), &ErrorKind::SerdeError);
+
+    // A rule that descends into a scalar
+    let nested = mapping().move_field("port", "host.port");
+    let error = nested.convert::<_, Server>(&dto()).expect_err("Moved field into string?!");
+    assert_eq!(error.err(), &ErrorKind::TypeError);
+    assert_eq!(error.path().expect("Missing error path?!").to_string(), "$.host");
+}
@@ -0,0 +1,142 @@
+#[macro_use] extern crate serde_derive;
+mod common;
+
+use common::{ map, string };
+use serde_dynamic_typing::{
+    error::ErrorKind,
+    migration::{ self, Migrations },
+    typing::{ Integer, Utf8String }
+};
+use std::{ convert::TryFrom, sync::OnceLock, thread };
+
+
+/// A server config in the latest version
+#[derive(Debug, PartialEq, Deserialize)]
+struct Server {
+    version: u64,
+    host: String,
+    port: u16,
+    mode: Mode
+}
+/// A server mode
+#[derive(Debug, PartialEq, Deserialize)]
+enum Mode {
+    Remote(u32)
+}
+
+
+/// Creates the migrations
+fn migrations() -> Migrations {
+    Migrations::new("version")
+        .register(0, migration::rename_key("hostname", "address"))
+        .register(1, migration::split_field("address", ["host", "port"], |address| {
+            let address = Utf8String::try_from(address)?;
+            let (host, port) = address.as_str().split_once(':').unwrap_or((address.as_str(), "80"));
+            let port: u16 = port.parse().map_err(|_| ErrorKind::InvalidData)?;
+            Ok(vec![string(host), Integer::from(port).into()])
+        }))
+        .register(2, migration::wrap_enum("timeout", "Remote"))
+        .register(3, migration::rename_key("timeout", "mode"))
+}
+
+
+#[test]
+fn migrate() {
+    let migrations = migrations();
+    assert_eq!(migrations.latest(), 4);
+
+    // Migrate from the first version
+    let document = map(vec![
+        ("version", Integer::from(0u8).into()),
+        ("hostname", string("example.org:443")),
+        ("timeout", Integer::from(30u8).into())
+    ]);
+    let server: Server = migrations.migrate_and_deserialize(document).expect("Failed to migrate document");
+    assert_eq!(server, Server { version: 4, host: String::from("example.org"), port: 443, mode: Mode::Remote(30) });
+
+    // Migrate from an intermediate version
+    let document = map(vec![
+        ("version", Integer::from(2u8).into()),
+        ("host", string("localhost")),
+        ("port", Integer::from(80u8).into()),
+        ("timeout", Integer::from(5u8).into())
+    ]);
+    let server: Server = migrations.migrate_and_deserialize(document).expect("Failed to migrate document");
+    assert_eq!(server, Server { version: 4, host: String::from("localhost"), port: 80, mode: Mode::Remote(5) });
+}
+
+
+#[test]
+fn shared() {
+    static MIGRATIONS: OnceLock<Migrations> = OnceLock::new();
+    let migrations = MIGRATIONS.get_or_init(migrations);
+
+    let document = map(vec![
+        ("version", Integer::from(3u8).into()),
+        ("host", string("localhost")),
+        ("port", Integer::from(80u8).into()),
+        ("mode", string("Remote"))
+    ]);
+    let migrated = thread::spawn(move || migrations.migrate(document).expect("Failed to migrate document"))
+        .join().expect("Failed to migrate document in another thread");
+    assert_eq!(migrated, map(vec![
+        ("version", Integer::from(4u8).into()),
+        ("host", string("localhost")),
+        ("port", Integer::from(80u8).into()),
+        ("mode", string("Remote"))
+    ]));
+}
+
+
+#[test]
+fn invalid_version() {
+    let migrations = migrations();
+
+    // A missing version
+    let error = migrations.migrate(map(vec![("host", string("localhost"))])).expect_err("Migrated unversioned?!");
+    assert_eq!(error.err(), &ErrorKind::NoSuchKey);
+
+    // A version from the future
+    let document = map(vec![("version", Integer::from(5u8).into())]);
+    let error = migrations.migrate(document).expect_err("Migrated document from the future?!");
+    assert_eq!(error.err(), &ErrorKind::InvalidData);
+
+    // A gap in the migration chain
+    let migrations = Migrations::new("version").register(1, Ok);
+    let document = map(vec![("version", Integer::from(0u8).into())]);
+    let error = migrations.migrate(document).expect_err("Migrated across a gap?!");
+    assert_eq!(error.err(), &ErrorKind::NoSuchKey);
+}
+
+
+#[test]
+fn base_version() {
+    let document = map(vec![("version", Integer::from(1u8).into()), ("host", string("localhost"))]);
+
+    // A registry without steps accepts documents in its base version
+    let migrations = Migrations::new("version").with_base_version(1);
+    assert_eq!(migrations.latest(), 1);
+    let migrated = migrations.migrate(document.clone()).expect("Failed to migrate document in the base version");
+    assert_eq!(migrated, document);
+
+    // Registered steps determine the latest version
+    let migrations = migrations.register(1, migration::rename_key("host", "address"));
+    assert_eq!(migrations.latest(), 2);
+    let migrated = migrations.migrate(document).expect("Failed to migrate document");
+    assert_eq!(migrated, map(vec![("version", Integer::from(2u8).into()), ("address", string("localhost"))]));
+}
+
+
+#[test]
+#[should_panic(expected = "Cannot register a migration step from the largest possible version")]
+fn last_version() {
+    let _ = Migrations::new("version").register(u64::MAX, Ok);
+}
+
+
+#[test]
+fn invalid_step() {
+    let document = map(vec![("version", Integer::from(1u8).into()), ("address", string("localhost:http"))]);
+    let error = migrations().migrate(document).expect_err("Migrated invalid port?!");
+    assert_eq!(error.err(), &ErrorKind::InvalidData);
+}
@@ -0,0 +1,138 @@
+use serde::{
+    de::{ Deserialize, Deserializer, MapAccess, Visitor },
+    ser::{ Serialize, SerializeMap, Serializer }
+};
+use serde_dynamic_typing::{
+    error::ErrorKind,
+    typing::{ AnyValue, Integer, Map, Utf8String }
+};
+use std::{ fmt::{ self, Formatter }, iter::FromIterator };
+
+
+/// A `Serialize` implementation that misuses `SerializeMap` in the given way
+enum BrokenMap {
+    /// Serializes two values for a single key
+    ValueTwice,
+    /// Serializes a value without a key
+    ValueWithoutKey,
+    /// Serializes two keys without a value
+    KeyTwice,
+    /// Ends the map while a key has no value
+    PendingKey
+}
+impl Serialize for BrokenMap {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let mut map = serializer.serialize_map(None)?;
+        match self {
+            Self::ValueTwice => {
+                map.serialize_key("key")?;
+                map.serialize_value(&0)?;
+                map.serialize_value(&1)?;
+            },
+            Self::ValueWithoutKey => map.serialize_value(&0)?,
+            Self::KeyTwice => {
+                map.serialize_key("key0")?;
+                map.serialize_key("key1")?;
+            },
+            Self::PendingKey => map.serialize_key("key")?
+        }
+        map.end()
+    }
+}
+
+
+/// A `Deserialize` implementation that misuses `MapAccess` in the given way
+#[derive(Debug)]
+struct BrokenVisitor {
+    /// Whether to read a value without a key or two keys without a value
+    value_without_key: bool
+}
+impl<'de> Visitor<'de> for BrokenVisitor {
+    type Value = ();
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "a map")
+    }
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error> where A: MapAccess<'de> {
+        match self.value_without_key {
+            true => map.next_value::<u8>().map(|_| ()),
+            false => {
+                map.next_key::<String>()?;
+                map.next_key::<String>().map(|_| ())
+            }
+        }
+    }
+}
+/// A type that reads a value without a key
+#[derive(Debug)]
+struct ValueWithoutKey;
+impl<'de> Deserialize<'de> for ValueWithoutKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        deserializer.deserialize_map(BrokenVisitor { value_without_key: true }).map(|_| Self)
+    }
+}
+/// A type that reads two keys without a value
+#[derive(Debug)]
+struct KeyTwice;
+impl<'de> Deserialize<'de> for KeyTwice {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        deserializer.deserialize_map(BrokenVisitor { value_without_key: false }).map(|_| Self)
+    }
+}
+
+
+#[test]
+fn broken_serialize() {
+    for broken in [BrokenMap::ValueTwice, BrokenMap::ValueWithoutKey, BrokenMap::KeyTwice, BrokenMap::PendingKey] {
+        let error = serde_dynamic_typing::to_typed(&broken).expect_err("Serialized broken map?!");
+        assert_eq!(error.err(), &ErrorKind::SerdeError);
+    }
+}
+
+
+#[test]
+fn broken_deserialize() {
+    let map = AnyValue::Map(Map::from_iter(vec![
+        (Utf8String::from("key0").into(), Integer::from(0).into()),
+        (Utf8String::from("key1").into(), Integer::from(1).into())
+    ]));
+
+    let error = serde_dynamic_typing::from_typed::<ValueWithoutKey>(map.clone())
+        .expect_err("Deserialized value without key?!");
+    assert_eq!(error.err(), &ErrorKind::SerdeError);
+
+    let error = serde_dynamic_typing::from_typed::<KeyTwice>(map).expect_err("Deserialized two keys without value?!");
+    assert_eq!(error.err(), &ErrorKind::SerdeError);
+}
+
+
+#[test]
+fn serialize_entry() {
+    /// A map that is serialized with `serialize_entry`
+    struct Entries;
+    impl Serialize for Entries {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+            let mut map = serializer.serialize_map(Some(2))?;
+            map.serialize_entry("key0", &0)?;
+            map.serialize_entry("key1", &1)?;
+            map.end()
+        }
+    }
+
+    let expected = AnyValue::Map(Map::from_iter(vec![
+        (Utf8String::from("key0").into(), Integer::from(0).into()),
+        (Utf8String::from("key1").into(), Integer::from(1).into())
+    ]));
+    let serialized = serde_dynamic_typing::to_typed(&Entries).expect("Failed to serialize map entries");
+    assert_eq!(serialized, expected);
+}
+
+
+#[test]
+fn integer_extremes() {
+    assert_eq!(Integer::from(i128::MIN), Integer::SignedInteger(i128::MIN));
+    assert_eq!(Integer::from(u128::MAX), Integer::UnsignedInteger(u128::MAX));
+    assert_eq!(Integer::from(isize::MIN), Integer::SignedInteger(isize::MIN as i128));
+    assert_eq!(Integer::from(-1i8), Integer::SignedInteger(-1));
+    assert_eq!(Integer::from(1i8), Integer::UnsignedInteger(1));
+}
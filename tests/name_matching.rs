@@ -0,0 +1,108 @@
+#[macro_use] extern crate serde_derive;
+mod common;
+
+use common::{ map, string };
+use serde_dynamic_typing::{
+    de::{ self, NameMatching, Options },
+    error::ErrorKind,
+    typing::{ AnyValue, Enumeration, Integer }
+};
+
+
+/// A struct with multi-word fields
+#[derive(Debug, PartialEq, Deserialize)]
+struct Server {
+    host_name: String,
+    port: u16,
+    mode: Mode
+}
+
+
+/// An enum with multi-word variants
+#[derive(Debug, PartialEq, Deserialize)]
+enum Mode {
+    ReadOnly,
+    ReadWrite { max_clients: u8 }
+}
+
+
+/// A struct with fields that are only distinguishable by their naming convention
+#[derive(Debug, PartialEq, Deserialize)]
+#[allow(non_snake_case)]
+struct Ambiguous {
+    user_name: String,
+    userName: String
+}
+
+
+#[test]
+fn exact_by_default() {
+    let value = map(vec![("hostName", string("localhost")), ("port", Integer::from(80u8).into())]);
+    let error = de::from_typed::<Server>(value).expect_err("Matched field names inexactly?!");
+    assert_eq!(error.err(), &ErrorKind::NoSuchKey);
+}
+
+
+#[test]
+fn case_insensitive() {
+    let options = Options::new().with_name_matching(NameMatching::CaseInsensitive);
+    let value = map(vec![
+        ("HOST_NAME", string("localhost")),
+        ("Port", Integer::from(80u8).into()),
+        ("MODE", AnyValue::Enum(Enumeration::new("readonly")))
+    ]);
+    let (server, _) = de::from_typed_with::<Server>(value, &options).expect("Failed to match names");
+    assert_eq!(server, Server { host_name: String::from("localhost"), port: 80, mode: Mode::ReadOnly });
+
+    // Naming conventions are not matched
+    let value = map(vec![
+        ("hostName", string("localhost")),
+        ("port", Integer::from(80u8).into()),
+        ("mode", AnyValue::Enum(Enumeration::new("ReadOnly")))
+    ]);
+    de::from_typed_with::<Server>(value, &options).expect_err("Matched naming convention?!");
+}
+
+
+#[test]
+fn naming_convention() {
+    let options = Options::new().with_name_matching(NameMatching::NamingConvention);
+    for host_name in ["host_name", "hostName", "host-name", "HOST_NAME", "HostName"] {
+        let value = map(vec![
+            (host_name, string("localhost")),
+            ("port", Integer::from(80u8).into()),
+            ("mode", AnyValue::Enum(Enumeration::with_value("read-write", map(vec![
+                ("maxClients", Integer::from(7u8).into())
+            ]))))
+        ]);
+        let (server, _) = de::from_typed_with::<Server>(value, &options).expect("Failed to match names");
+        assert_eq!(server.host_name, "localhost");
+        assert_eq!(server.mode, Mode::ReadWrite { max_clients: 7 });
+    }
+}
+
+
+#[test]
+fn ambiguous() {
+    let options = Options::new().with_name_matching(NameMatching::NamingConvention);
+
+    // Multiple keys match the same field
+    let value = map(vec![
+        ("host_name", string("localhost")),
+        ("hostName", string("localhost")),
+        ("port", Integer::from(80u8).into()),
+        ("mode", AnyValue::Enum(Enumeration::new("ReadOnly")))
+    ]);
+    let error = de::from_typed_with::<Server>(value, &options).expect_err("Accepted ambiguous keys?!");
+    assert_eq!(error.err(), &ErrorKind::InvalidData);
+
+    // A key matches multiple fields
+    let value = map(vec![("USER_NAME", string("a")), ("userName", string("b"))]);
+    let error = de::from_typed_with::<Ambiguous>(value, &options).expect_err("Accepted ambiguous field?!");
+    assert_eq!(error.err(), &ErrorKind::InvalidData);
+
+    // Exact matches are never ambiguous
+    let value = map(vec![("user_name", string("a")), ("userName", string("b"))]);
+    let (ambiguous, _) = de::from_typed_with::<Ambiguous>(value, &options).expect("Failed to match exact names");
+    assert_eq!(ambiguous, Ambiguous { user_name: String::from("a"), userName: String::from("b") });
+}
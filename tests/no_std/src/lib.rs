@@ -0,0 +1,17 @@
+//! Ensures that `serde_dynamic_typing` builds and can be used in a `#![no_std]` crate with `alloc`
+//!
+//! Build with `cargo build --manifest-path tests/no_std/Cargo.toml`
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{ collections::BTreeMap, string::String, vec::Vec };
+use serde_dynamic_typing::{ AnyValue, error::Result };
+
+
+/// Converts `map` into an `AnyValue` and back
+pub fn roundtrip(map: &BTreeMap<String, Vec<u64>>) -> Result<BTreeMap<String, Vec<u64>>> {
+    let typed: AnyValue = serde_dynamic_typing::to_typed(map)?;
+    serde_dynamic_typing::from_typed(typed)
+}
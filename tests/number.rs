@@ -0,0 +1,82 @@
+use serde_dynamic_typing::typing::{ AnyValue, Float, Integer, Kind, Number, Utf8String };
+use std::{ cmp::Ordering, convert::TryFrom };
+
+
+#[test]
+fn integer_arithmetic() {
+    let max = Integer::from(u128::MAX);
+    let min = Integer::from(i128::MIN);
+
+    // Crossing the `u128`/`i128` split
+    assert_eq!(Integer::from(3u8).checked_sub(Integer::from(5u8)).unwrap(), Integer::from(-2i8));
+    assert_eq!(Integer::from(-2i8).checked_add(Integer::from(5u8)).unwrap(), Integer::from(3u8));
+    assert_eq!(max.checked_sub(max).unwrap(), Integer::from(0u8));
+    assert_eq!(min.checked_add(max).unwrap(), Integer::from(i128::MAX as u128));
+    assert_eq!(Integer::from(-6i8).checked_div(Integer::from(-4i8)).unwrap(), Integer::from(1u8));
+    assert_eq!(Integer::from(-7i8).checked_rem(Integer::from(4u8)).unwrap(), Integer::from(-3i8));
+    assert_eq!(Integer::from(i128::MAX as u128 + 1).checked_neg().unwrap(), min);
+
+    // Overflows and invalid operations
+    assert!(max.checked_add(Integer::from(1u8)).is_err());
+    assert!(min.checked_sub(Integer::from(1u8)).is_err());
+    assert!(min.checked_mul(Integer::from(2u8)).is_err());
+    assert!(max.checked_neg().is_err());
+    assert!(Integer::from(1u8).checked_div(Integer::SignedInteger(0)).is_err());
+}
+
+
+#[test]
+fn float_arithmetic() {
+    let value = Float::from(1.5);
+    assert_eq!(*value.checked_mul(Float::from(2.0)).unwrap(), 3.0);
+    assert!(value.checked_div(Float::from(0.0)).is_err());
+    assert!(Float::from(f64::MAX).checked_add(Float::from(f64::MAX)).is_err());
+    assert!(Float::from(f64::NAN).checked_neg().is_err());
+}
+
+
+#[test]
+fn mixed_comparison() {
+    let two_pow_53 = 9007199254740992u64;
+
+    // Integers are compared by value regardless of their representation
+    assert_eq!(Number::from(Integer::SignedInteger(7)), Number::from(Integer::UnsignedInteger(7)));
+    assert_eq!(Integer::from(7u8), Float::from(7.0));
+    assert!(Integer::from(7u8) < Float::from(7.5));
+    assert!(Integer::from(-8i8) < Float::from(-7.5));
+    assert!(Float::from(-7.5) > Integer::from(-8i8));
+
+    // Values above 2^53 are compared exactly instead of being rounded to `f64`
+    assert_ne!(Integer::from(two_pow_53 + 1), Float::from(two_pow_53 as f64));
+    assert!(Integer::from(two_pow_53 + 1) > Float::from(two_pow_53 as f64));
+    assert!(Integer::from(u128::MAX) < Float::from(f64::MAX));
+    assert!(Integer::from(i128::MIN) > Float::from(f64::MIN));
+
+    // NaN is unordered but sorted to the edges by `total_cmp`
+    let nan = Number::from(Float::from(f64::NAN));
+    assert_ne!(nan, nan);
+    assert_eq!(nan.partial_cmp(&Number::from(Integer::from(0u8))), None);
+    assert_eq!(nan.total_cmp(&Number::from(Float::from(f64::INFINITY))), Ordering::Greater);
+    assert_eq!(Number::from(Float::from(-f64::NAN)).total_cmp(&Number::from(Integer::from(i128::MIN))), Ordering::Less);
+}
+
+
+#[test]
+fn mixed_arithmetic() {
+    let integer = Number::from(Integer::from(3u8));
+    let float = Number::from(Float::from(0.5));
+
+    assert_eq!(integer.checked_add(integer).unwrap(), Number::from(Integer::from(6u8)));
+    assert_eq!(integer.checked_mul(float).unwrap(), Number::from(Float::from(1.5)));
+    assert_eq!(float.to_integer().ok(), None);
+    assert_eq!(Number::from(Float::from(-3.0)).to_integer().unwrap(), Integer::from(-3i8));
+    assert!(Number::from(Float::from(1e40)).to_integer().is_err());
+}
+
+
+#[test]
+fn not_a_number() {
+    let value = AnyValue::Utf8String(Utf8String::from("7"));
+    let error = Number::try_from(value).expect_err("Converted string to number?!");
+    assert_eq!((error.expected(), error.found()), (Some(Kind::Float), Some(Kind::Utf8String)));
+}
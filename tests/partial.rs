@@ -0,0 +1,99 @@
+#[macro_use] extern crate serde_derive;
+mod common;
+
+use common::{ map, string };
+use serde_dynamic_typing::{
+    error::ErrorKind,
+    partial,
+    typing::{ AnyValue, Enumeration, Integer, Sequence }
+};
+
+
+/// A server config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Server {
+    host: String,
+    port: u16,
+    tags: Vec<String>,
+    mode: Mode
+}
+
+
+/// A server mode
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Mode {
+    Local,
+    Remote { timeout: u32, retries: u8 }
+}
+
+
+/// Creates a server
+fn server() -> Server {
+    Server {
+        host: String::from("localhost"), port: 80, tags: vec![String::from("a"), String::from("b")],
+        mode: Mode::Remote { timeout: 30, retries: 3 }
+    }
+}
+
+
+#[test]
+fn apply() {
+    let update = map(vec![
+        ("port", Integer::from(443u16).into()),
+        ("tags", AnyValue::Sequence(Sequence::new([string("c")]))),
+        ("mode", AnyValue::Enum(Enumeration::with_value("Remote", map(vec![("retries", Integer::from(5u8).into())]))))
+    ]);
+    let mut target = server();
+    let changed = partial::apply_partial(&mut target, update).expect("Failed to apply update");
+
+    // Validate the target and the changes
+    let expected = Server {
+        host: String::from("localhost"), port: 443, tags: vec![String::from("c")],
+        mode: Mode::Remote { timeout: 30, retries: 5 }
+    };
+    assert_eq!(target, expected);
+    let changed: Vec<_> = changed.iter().map(ToString::to_string).collect();
+    assert_eq!(changed, ["$.mode.Remote.retries", "$.port", "$.tags"]);
+}
+
+
+#[test]
+fn apply_in_place() {
+    let update = map(vec![
+        ("host", string("localhost")),
+        ("mode", AnyValue::Enum(Enumeration::new("Local")))
+    ]);
+    let mut target = server();
+    let changed = partial::apply_partial_in_place(&mut target, update).expect("Failed to apply update");
+    assert_eq!(target, Server { mode: Mode::Local, ..server() });
+    let changed: Vec<_> = changed.iter().map(ToString::to_string).collect();
+    assert_eq!(changed, ["$.mode"]);
+}
+
+
+#[test]
+fn unchanged() {
+    let update = map(vec![("host", string("localhost")), ("port", Integer::from(80u8).into())]);
+    let mut target = server();
+    let changed = partial::apply_partial(&mut target, update).expect("Failed to apply update");
+    assert!(changed.is_empty());
+    assert_eq!(target, server());
+
+    // Keys that are unknown to the target type are ignored
+    let update = map(vec![("unknown", string("value")), ("port", Integer::from(443u16).into())]);
+    let changed = partial::apply_partial(&mut target, update).expect("Failed to apply update");
+    let changed: Vec<_> = changed.iter().map(ToString::to_string).collect();
+    assert_eq!(changed, ["$.port"]);
+    assert_eq!(target, Server { port: 443, ..server() });
+}
+
+
+#[test]
+fn invalid() {
+    let update = map(vec![("host", string("example.org")), ("port", string("443"))]);
+    let mut target = server();
+    let error = partial::apply_partial(&mut target, update).expect_err("Applied invalid update?!");
+    assert_eq!(error.err(), &ErrorKind::TypeError);
+    assert_eq!(error.path().expect("Missing error path?!").to_string(), "$.port");
+    assert_eq!(target, server());
+}
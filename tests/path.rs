@@ -0,0 +1,111 @@
+#[macro_use] extern crate serde_derive;
+mod common;
+
+use common::{ map, string };
+use serde_dynamic_typing::{
+    de::{ self, NameMatching },
+    error::ErrorKind,
+    path::Segment,
+    typing::{ AnyValue, Enumeration, Integer, Map, Sequence, Utf8String }
+};
+use std::{ collections::BTreeMap, iter::FromIterator };
+
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Server {
+    host: String,
+    port: u16
+}
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+enum Listener {
+    Tcp(Server),
+    Unix { path: String }
+}
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Config {
+    servers: Vec<Server>,
+    listener: Option<Listener>
+}
+
+
+/// Creates a config map
+fn config(servers: Vec<AnyValue>, listener: AnyValue) -> AnyValue {
+    map(vec![("servers", Sequence::from(servers).into()), ("listener", listener)])
+}
+
+
+#[test]
+fn sequence_and_map() {
+    let value = config(vec![
+        map(vec![("host", string("a")), ("port", Integer::from(1u16).into())]),
+        map(vec![("host", string("b")), ("port", Integer::from(2u16).into())]),
+        map(vec![("host", string("c")), ("port", string("80"))])
+    ], AnyValue::None);
+
+    let error = serde_dynamic_typing::from_typed::<Config>(value).expect_err("Deserialized an invalid config");
+    assert_eq!(*error.err(), ErrorKind::TypeError);
+    assert_eq!(error.path().expect("Missing error path").to_string(), "$.servers[2].port");
+}
+
+
+#[test]
+fn missing_field() {
+    let value = config(vec![map(vec![("host", string("a"))])], AnyValue::None);
+
+    let error = serde_dynamic_typing::from_typed::<Config>(value).expect_err("Deserialized an invalid config");
+    assert_eq!(*error.err(), ErrorKind::NoSuchKey);
+    assert_eq!(error.path().expect("Missing error path").to_string(), "$.servers[0].port");
+}
+
+
+#[test]
+fn enumeration() {
+    let server = map(vec![("host", string("a")), ("port", Integer::from(-1i8).into())]);
+    let listener = Enumeration::with_value("Tcp", server);
+    let value = config(Vec::new(), listener.into());
+
+    let error = serde_dynamic_typing::from_typed::<Config>(value).expect_err("Deserialized an invalid config");
+    let path = error.path().expect("Missing error path");
+    assert_eq!(path.to_string(), "$.listener.Tcp.port");
+    assert_eq!(path.segments()[1], Segment::Variant("Tcp".to_string()));
+}
+
+
+#[test]
+fn field_names() {
+    // Struct fields are recorded under the field names requested by the deserialized type...
+    let value = config(vec![map(vec![("host", string("a")), ("port", string("80"))])], AnyValue::None);
+    let error = serde_dynamic_typing::from_typed::<Config>(value).expect_err("Deserialized an invalid config");
+    assert_eq!(error.path().expect("Missing error path").segments(), &[
+        Segment::Field("servers".to_string()), Segment::Index(0), Segment::Field("port".to_string())
+    ]);
+
+    // ...also if the key has been matched by the name matching strategy...
+    let options = de::Options::new().with_name_matching(NameMatching::CaseInsensitive);
+    let value = map(vec![("HOST", string("a")), ("PORT", string("80"))]);
+    let error = de::from_typed_with::<Server>(value, &options).expect_err("Deserialized an invalid server");
+    assert_eq!(error.path().expect("Missing error path").segments(), &[Segment::Field("port".to_string())]);
+
+    // ...while map entries are recorded under their keys
+    let value = map(vec![("port", string("80"))]);
+    let error = serde_dynamic_typing::from_typed::<BTreeMap<String, u16>>(value)
+        .expect_err("Deserialized an invalid map");
+    assert_eq!(error.path().expect("Missing error path").segments(), &[Segment::Key(string("port"))]);
+}
+
+
+#[test]
+fn root() {
+    let error = serde_dynamic_typing::from_typed::<u8>(AnyValue::None).expect_err("Deserialized an invalid integer");
+    assert!(error.path().expect("Missing error path").is_root());
+
+    let key = AnyValue::Map(Map::from_iter(vec![
+        (Integer::from(7u8).into(), Utf8String::from("seven").into())
+    ]));
+    let error = serde_dynamic_typing::from_typed::<std::collections::BTreeMap<u8, u8>>(key)
+        .expect_err("Deserialized an invalid map");
+    assert_eq!(error.path().expect("Missing error path").to_string(), "$[7]");
+}
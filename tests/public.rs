@@ -0,0 +1,100 @@
+#[macro_use] extern crate serde_derive;
+
+use serde::{
+    Deserialize, Serialize,
+    de::IntoDeserializer
+};
+use serde_dynamic_typing::{
+    de::Deserializer, ser::Serializer,
+    error::ErrorKind,
+    typing::{ AnyValue, Enumeration, Integer, Map, Utf8String },
+    EnumRepr, Limits
+};
+use std::iter::FromIterator;
+
+
+/// An enum with all kinds of variants
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Shape {
+    Empty,
+    Circle(u8),
+    Line(u8, u8),
+    Rect { width: u8, height: u8 }
+}
+
+
+/// Creates a single-entry map `{key: value}`
+fn entry(key: &str, value: AnyValue) -> AnyValue {
+    AnyValue::Map(Map::from_iter([(Utf8String::from(key).into(), value)]))
+}
+
+
+#[test]
+fn native_enums() {
+    let value = Shape::Circle(7).serialize(Serializer::new()).expect("Failed to serialize enum");
+    assert_eq!(value, AnyValue::Enum(Enumeration::with_value("Circle", Integer::from(7u8))));
+    let shape = Shape::deserialize(Deserializer::new(value)).expect("Failed to deserialize enum");
+    assert_eq!(shape, Shape::Circle(7));
+}
+
+
+#[test]
+fn external_enums() {
+    let shapes = [Shape::Empty, Shape::Circle(7), Shape::Line(1, 2), Shape::Rect { width: 3, height: 4 }];
+    for shape in shapes {
+        let value = shape.serialize(Serializer::new().with_enum_repr(EnumRepr::External))
+            .expect("Failed to serialize enum");
+        match &shape {
+            Shape::Empty => assert_eq!(value, AnyValue::Utf8String(Utf8String::from("Empty"))),
+            Shape::Circle(radius) => assert_eq!(value, entry("Circle", Integer::from(*radius).into())),
+            _ => assert!(matches!(value, AnyValue::Map(_)), "Unexpected enum representation?!")
+        }
+
+        let deserialized = Shape::deserialize(Deserializer::new(value).with_enum_repr(EnumRepr::External))
+            .expect("Failed to deserialize enum");
+        assert_eq!(deserialized, shape);
+    }
+
+    // Native enums are still accepted
+    let native = AnyValue::Enum(Enumeration::new("Empty"));
+    let shape = Shape::deserialize(Deserializer::new(native).with_enum_repr(EnumRepr::External))
+        .expect("Failed to deserialize native enum");
+    assert_eq!(shape, Shape::Empty);
+
+    // ...but externally tagged enums are rejected by default
+    let error = Shape::deserialize(Deserializer::new(entry("Circle", Integer::from(7u8).into())))
+        .expect_err("Deserialized externally tagged enum?!");
+    assert_eq!(error.err(), &ErrorKind::TypeError);
+}
+
+
+#[test]
+fn configuration() {
+    // Human readable flag
+    assert!(serde::Serializer::is_human_readable(&Serializer::new().human_readable(true)));
+    assert!(!serde::Serializer::is_human_readable(&Serializer::new().human_readable(false)));
+    let deserializer = Deserializer::new(AnyValue::None).human_readable(true);
+    assert!(serde::Deserializer::is_human_readable(&deserializer));
+
+    // Limits are also enforced for the root value
+    let limits = Limits::new().max_string_len(4);
+    let error = "fives".serialize(Serializer::new().with_limits(limits)).expect_err("Serialized too long string?!");
+    assert_eq!(error.err(), &ErrorKind::LimitExceeded);
+    let error = String::deserialize(Deserializer::new(Utf8String::from("fives").into()).with_limits(limits))
+        .expect_err("Deserialized too long string?!");
+    assert_eq!(error.err(), &ErrorKind::LimitExceeded);
+}
+
+
+#[test]
+fn into_deserializer() {
+    let value: AnyValue = Integer::from(7u8).into();
+    let deserialized = u8::deserialize(value.into_deserializer()).expect("Failed to deserialize integer");
+    assert_eq!(deserialized, 7);
+
+    // Deserialize in place
+    let mut target = vec![1u8, 2, 3];
+    let value = AnyValue::Sequence(vec![AnyValue::from(Integer::from(4u8))].into());
+    Vec::deserialize_in_place(value.into_deserializer(), &mut target).expect("Failed to deserialize in place");
+    assert_eq!(target, vec![4]);
+}
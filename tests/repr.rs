@@ -0,0 +1,117 @@
+#[macro_use] extern crate serde_derive;
+mod common;
+
+use common::{ map, roundtrip_with, string };
+use serde::{ Serialize, de::DeserializeOwned };
+use serde_dynamic_typing::{
+    de, ser, repr,
+    error::ErrorKind,
+    typing::{ AnyValue, Enumeration, Integer, Sequence },
+    EnumRepr
+};
+use std::{ fmt::Debug, iter::FromIterator };
+
+
+/// An enum whose variants can be represented in all representations
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Event {
+    Started,
+    Moved { x: i32, y: i32 },
+    Cleared {},
+    Renamed(Name)
+}
+/// A newtype payload
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Name {
+    name: String
+}
+
+
+/// Round-trips `value` with `enum_repr` and asserts that the intermediate representation equals `expected`
+fn roundtrip<T>(value: T, enum_repr: &EnumRepr, expected: AnyValue)
+    where T: Debug + PartialEq + Serialize + DeserializeOwned
+{
+    let ser_options = ser::Options::new().with_enum_repr(enum_repr.clone());
+    let de_options = de::Options::new().with_enum_repr(enum_repr.clone());
+    assert_eq!(roundtrip_with(&value, &ser_options, &de_options), expected);
+}
+
+
+#[test]
+fn external() {
+    let repr = EnumRepr::External;
+    roundtrip(Event::Started, &repr, string("Started"));
+    roundtrip(Event::Moved { x: 1, y: -1 }, &repr, map(vec![
+        ("Moved", map(vec![("x", Integer::from(1).into()), ("y", Integer::from(-1).into())]))
+    ]));
+}
+
+
+#[test]
+fn internal() {
+    let repr = EnumRepr::internal("type");
+    roundtrip(Event::Started, &repr, map(vec![("type", string("Started"))]));
+    roundtrip(Event::Moved { x: 1, y: -1 }, &repr, map(vec![
+        ("type", string("Moved")), ("x", Integer::from(1).into()), ("y", Integer::from(-1).into())
+    ]));
+    roundtrip(Event::Renamed(Name { name: String::from("test") }), &repr, map(vec![
+        ("type", string("Renamed")), ("name", string("test"))
+    ]));
+    roundtrip(Event::Cleared {}, &repr, map(vec![("type", string("Cleared"))]));
+
+    // Unit variants may also be bare strings
+    let (event, _) = de::from_typed_with::<Event>(string("Started"), &de::Options::new().with_enum_repr(repr.clone()))
+        .expect("Failed to deserialize bare unit variant");
+    assert_eq!(event, Event::Started);
+
+    // Values without named fields cannot be tagged
+    let error = ser::to_typed_with(&Some(Ok::<u8, ()>(7)), &ser::Options::new().with_enum_repr(repr.clone()))
+        .expect_err("Serialized internally tagged tuple?!");
+    assert_eq!(error.err(), &ErrorKind::TypeError);
+
+    // The tag is required
+    let value = map(vec![("x", Integer::from(1).into())]);
+    let error = de::from_typed_with::<Event>(value, &de::Options::new().with_enum_repr(repr))
+        .expect_err("Deserialized enum without tag?!");
+    assert_eq!(error.err(), &ErrorKind::NoSuchKey);
+}
+
+
+#[test]
+fn adjacent() {
+    let repr = EnumRepr::adjacent("t", "c");
+    roundtrip(Event::Started, &repr, map(vec![("t", string("Started"))]));
+    roundtrip(Event::Moved { x: 1, y: -1 }, &repr, map(vec![
+        ("t", string("Moved")), ("c", map(vec![("x", Integer::from(1).into()), ("y", Integer::from(-1).into())]))
+    ]));
+    roundtrip(Ok::<u8, ()>(7), &repr, map(vec![("t", string("Ok")), ("c", Integer::from(7u8).into())]));
+    roundtrip(Event::Cleared {}, &repr, map(vec![("t", string("Cleared")), ("c", map(vec![]))]));
+
+    // Unexpected entries are rejected
+    let value = map(vec![("t", string("Ok")), ("c", Integer::from(7u8).into()), ("x", AnyValue::None)]);
+    let error = de::from_typed_with::<Result<u8, ()>>(value, &de::Options::new().with_enum_repr(repr))
+        .expect_err("Deserialized enum with unexpected entries?!");
+    assert_eq!(error.err(), &ErrorKind::InvalidData);
+}
+
+
+#[test]
+fn tree_conversion() {
+    let native = AnyValue::Sequence(Sequence::from_iter(vec![
+        AnyValue::Enum(Enumeration::new("Started")),
+        AnyValue::Enum(Enumeration::with_value("Renamed", map(vec![("name", string("test"))])))
+    ]));
+    let repr = EnumRepr::internal("type");
+
+    // Encode the native enums
+    let encoded = repr::encode_enums(native.clone(), &repr).expect("Failed to encode enums");
+    assert_eq!(encoded, AnyValue::Sequence(Sequence::from_iter(vec![
+        map(vec![("type", string("Started"))]),
+        map(vec![("type", string("Renamed")), ("name", string("test"))])
+    ])));
+
+    // Decode the tagged maps again
+    let is_tagged = |value: &AnyValue| matches!(value, AnyValue::Map(map) if map.contains_key(&string("type")));
+    let decoded = repr::decode_enums(encoded, &repr, is_tagged).expect("Failed to decode enums");
+    assert_eq!(decoded, native);
+}
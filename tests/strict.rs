@@ -0,0 +1,111 @@
+use serde::{
+    de::{ Deserialize, Deserializer, MapAccess, SeqAccess, Visitor },
+    ser::{ Serialize, SerializeStruct, Serializer }
+};
+use serde_dynamic_typing::{
+    de, ser,
+    error::ErrorKind,
+    typing::{ AnyValue, Integer, Map, Sequence, Utf8String }
+};
+use std::{ fmt::{ self, Formatter }, iter::FromIterator };
+
+
+/// A type that only reads the first element of a sequence or the first entry of a map
+#[derive(Debug, PartialEq, Eq)]
+struct First(u8);
+impl<'de> Deserialize<'de> for First {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        /// The visitor for `First`
+        struct FirstVisitor;
+        impl<'de> Visitor<'de> for FirstVisitor {
+            type Value = First;
+
+            fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+                write!(f, "a non-empty sequence or map")
+            }
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error> where A: SeqAccess<'de> {
+                let first = seq.next_element()?.unwrap_or_default();
+                Ok(First(first))
+            }
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error> where A: MapAccess<'de> {
+                let first = map.next_entry::<String, u8>()?.map(|(_, value)| value).unwrap_or_default();
+                Ok(First(first))
+            }
+        }
+        deserializer.deserialize_any(FirstVisitor)
+    }
+}
+
+
+/// A struct that announces more fields than it serializes
+struct Liar;
+impl Serialize for Liar {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let mut structure = serializer.serialize_struct("Liar", 2)?;
+        structure.serialize_field("field", &0)?;
+        structure.end()
+    }
+}
+
+
+/// Creates a sequence of the integers `0..len`
+fn sequence(len: u8) -> AnyValue {
+    AnyValue::Sequence(Sequence::from_iter((0..len).map(|i| Integer::from(i).into())))
+}
+
+
+#[test]
+fn tuple_length() {
+    let strict = de::Options::new().strict(true);
+
+    // A sequence that is too long is truncated by default, but rejected in strict mode
+    let (value, _) = de::from_typed_with::<(u8, u8)>(sequence(3), &de::Options::new())
+        .expect("Failed to deserialize tuple");
+    assert_eq!(value, (0, 1));
+    let error = de::from_typed_with::<(u8, u8)>(sequence(3), &strict).expect_err("Deserialized too long tuple?!");
+    assert_eq!(error.err(), &ErrorKind::InvalidData);
+
+    // A sequence that is too short is always rejected
+    de::from_typed_with::<(u8, u8)>(sequence(1), &strict).expect_err("Deserialized too short tuple?!");
+    let (value, _) = de::from_typed_with::<(u8, u8)>(sequence(2), &strict).expect("Failed to deserialize tuple");
+    assert_eq!(value, (0, 1));
+}
+
+
+#[test]
+fn trailing_data() {
+    let strict = de::Options::new().strict(true);
+
+    // Trailing sequence elements
+    let (value, _) = de::from_typed_with::<First>(sequence(3), &de::Options::new())
+        .expect("Failed to deserialize first element");
+    assert_eq!(value, First(0));
+    let error = de::from_typed_with::<First>(sequence(3), &strict).expect_err("Ignored trailing elements?!");
+    assert_eq!(error.err(), &ErrorKind::InvalidData);
+    assert_eq!(error.path().expect("Missing error location").to_string(), "$[1]");
+
+    // Trailing map entries
+    let map = AnyValue::Map(Map::from_iter(vec![
+        (Utf8String::from("a").into(), Integer::from(0).into()),
+        (Utf8String::from("b").into(), Integer::from(1).into())
+    ]));
+    let (value, _) = de::from_typed_with::<First>(map.clone(), &de::Options::new())
+        .expect("Failed to deserialize first entry");
+    assert_eq!(value, First(0));
+    let error = de::from_typed_with::<First>(map, &strict).expect_err("Ignored trailing entries?!");
+    assert_eq!(error.err(), &ErrorKind::InvalidData);
+    assert_eq!(error.path().expect("Missing error location").to_string(), "$.b");
+}
+
+
+#[test]
+fn announced_length() {
+    let strict = ser::Options::new().strict(true);
+
+    ser::to_typed_with(&Liar, &ser::Options::new()).expect("Failed to serialize struct");
+    let error = ser::to_typed_with(&Liar, &strict).expect_err("Serialized struct with wrong length?!");
+    assert_eq!(error.err(), &ErrorKind::SerdeError);
+
+    let serialized = ser::to_typed_with(&(0u8, 1u8), &strict).expect("Failed to serialize tuple");
+    assert_eq!(serialized, sequence(2));
+}